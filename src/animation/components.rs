@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// A run of cells in a texture atlas, played one after another.
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    pub atlas: Handle<TextureAtlas>,
+    pub first: usize,
+    pub last: usize,
+    pub frame_time: Duration,
+    pub looping: bool,
+}
+
+impl AnimationClip {
+    /// Play the clip through once and stop on its last frame.
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+}
+
+/// The named clips an entity can show and which one it is showing.
+/// Gameplay systems pick a clip with `play`; `animation_system` switches the
+/// sprite's atlas and steps through the frames.
+#[derive(Component, Debug)]
+pub struct AnimationController {
+    clips: HashMap<String, AnimationClip>,
+    current: String,
+    timer: Timer,
+    started: bool,
+    finished: bool,
+}
+
+impl AnimationController {
+    pub fn new(name: &str, clip: AnimationClip) -> Self {
+        Self {
+            clips: HashMap::from_iter([(name.to_string(), clip)]),
+            current: name.to_string(),
+            timer: Timer::default(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    pub fn with_clip(mut self, name: &str, clip: AnimationClip) -> Self {
+        self.clips.insert(name.to_string(), clip);
+        self
+    }
+
+    pub fn has_clip(&self, name: &str) -> bool {
+        self.clips.contains_key(name)
+    }
+
+    /// Switch to the named clip, unless it is already playing. Panics if the
+    /// entity has no such clip.
+    pub fn play(&mut self, name: &str) {
+        if self.current != name {
+            self.restart(name);
+        }
+    }
+
+    /// Play the named clip from its first frame. Panics if the entity has no
+    /// such clip.
+    pub fn restart(&mut self, name: &str) {
+        assert!(self.has_clip(name), "no animation clip named \"{}\"", name);
+        self.current = name.to_string();
+        self.started = false;
+        self.finished = false;
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Whether the current clip has played through. Clips that loop never
+    /// finish.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Show the next frame if it is due, switching the atlas when a clip
+    /// starts. Returns true when a clip that doesn't loop has just finished.
+    pub fn step(
+        &mut self,
+        delta: Duration,
+        sprite: &mut TextureAtlasSprite,
+        atlas: &mut Handle<TextureAtlas>,
+    ) -> bool {
+        let clip = &self.clips[&self.current];
+
+        if !self.started {
+            if *atlas != clip.atlas {
+                *atlas = clip.atlas.clone();
+            }
+            sprite.index = clip.first;
+            self.timer = Timer::new(clip.frame_time, TimerMode::Repeating);
+            self.started = true;
+            return false;
+        }
+
+        if self.finished {
+            return false;
+        }
+
+        self.timer.tick(delta);
+        if !self.timer.just_finished() {
+            return false;
+        }
+
+        if sprite.index < clip.last {
+            sprite.index += 1;
+        } else if clip.looping {
+            sprite.index = clip.first;
+        } else {
+            self.finished = true;
+        }
+        self.finished
+    }
+}
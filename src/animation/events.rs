@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// A clip that doesn't loop has shown its last frame.
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub clip: String,
+}
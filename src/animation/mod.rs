@@ -0,0 +1,41 @@
+pub mod components;
+pub mod events;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::animation::events::AnimationFinished;
+use crate::animation::systems::animation_system;
+use crate::common::TickSet;
+use crate::game::states::GameState;
+use crate::game::GameEventSystemSet;
+
+/// Frame time of clips whose animation doesn't set one.
+pub const DEFAULT_FRAME_TIME: f32 = 0.1;
+
+/// Steps every `AnimationController`, once gameplay has picked this tick's
+/// clips.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct AnimationSystemSet;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AnimationFinished>()
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    AnimationSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(GameEventSystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
+            .add_system(
+                animation_system
+                    .in_set(AnimationSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::animation::components::AnimationController;
+use crate::animation::events::AnimationFinished;
+
+pub fn animation_system(
+    fixed_time: Res<FixedTime>,
+    mut animation_query: Query<(
+        Entity,
+        &mut AnimationController,
+        &mut TextureAtlasSprite,
+        &mut Handle<TextureAtlas>,
+    )>,
+    mut animation_finished_events: EventWriter<AnimationFinished>,
+) {
+    for (entity, mut controller, mut sprite, mut atlas) in animation_query.iter_mut() {
+        if controller.step(fixed_time.period, &mut sprite, &mut atlas) {
+            animation_finished_events.send(AnimationFinished {
+                entity,
+                clip: controller.current().to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::asset::AssetPlugin;
+use bevy::diagnostic::DiagnosticsPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::sprite::TextureAtlas;
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use bevy::window::{Window, WindowMode, WindowPlugin};
+use bevy::DefaultPlugins;
+
+use bevy_kira_audio::prelude::*;
+use leafwing_input_manager::plugin::ToggleActions;
+
+use crate::animation::AnimationPlugin;
+use crate::collision::CollisionPlugin;
+use crate::common::resources::{GameRng, Playfield};
+use crate::common::systems::setup_system;
+use crate::common::TIME_STEP;
+use crate::difficulty::resources::DifficultyPreset;
+use crate::difficulty::DifficultyPlugin;
+use crate::enemy::EnemyPlugin;
+use crate::game::resources::GameMode;
+use crate::game::states::{AppState, GameState};
+use crate::game::GamePlugin;
+use crate::item::ItemPlugin;
+use crate::player::actions::ControlAction;
+use crate::player::PlayerPlugin;
+use crate::replay::resources::{Replay, ReplayPlayback, ReplayRecorder};
+use crate::replay::ReplayPlugin;
+use crate::score::resources::{HighScoreFile, Initials};
+use crate::score::ScorePlugin;
+use crate::ui::UIPlugin;
+use crate::vfx::VfxPlugin;
+use crate::wave::WavePlugin;
+use crate::weapon::WeaponPlugin;
+use crate::world::WorldPlugin;
+
+/// How far a headless app's clock moves on each `App::update`, one
+/// `TIME_STEP` unless a test changes it.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HeadlessFrameTime(pub Duration);
+
+impl Default for HeadlessFrameTime {
+    fn default() -> Self {
+        Self(Duration::from_secs_f32(TIME_STEP))
+    }
+}
+
+/// Builds the game `App`, either in a fullscreen window or headless.
+///
+/// A headless app runs on `MinimalPlugins` with a virtual playfield and
+/// advances the clock by exactly `TIME_STEP` on every `App::update` (see
+/// `HeadlessFrameTime`), so tests can step frames and assert on the
+/// resulting game state. It keeps
+/// no high-score file unless given one.
+#[derive(Default)]
+pub struct GameAppBuilder {
+    headless_playfield: Option<Playfield>,
+    start_playing: bool,
+    seed: Option<u64>,
+    game_mode: GameMode,
+    difficulty: DifficultyPreset,
+    initials: Option<Initials>,
+    high_score_file: Option<HighScoreFile>,
+    record_path: Option<PathBuf>,
+    replay: Option<Replay>,
+}
+
+impl GameAppBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run without a window or renderer on a playfield of the given size.
+    pub fn headless(mut self, width: f32, height: f32) -> Self {
+        self.headless_playfield = Some(Playfield::new(width, height));
+        self
+    }
+
+    /// Skip the main menu and go straight into a running game.
+    pub fn start_playing(mut self) -> Self {
+        self.start_playing = true;
+        self
+    }
+
+    /// Seed all gameplay randomness, so the same seed always produces
+    /// the same enemy layout, background and diamond positions.
+    /// Without a seed a random one is picked and logged.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn game_mode(mut self, game_mode: GameMode) -> Self {
+        self.game_mode = game_mode;
+        self
+    }
+
+    /// Which difficulty curves to play on, `Normal` by default.
+    pub fn difficulty(mut self, difficulty: DifficultyPreset) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// The initials offered when a score makes the high-score table, "P1"
+    /// and "P2" by default.
+    pub fn initials(mut self, one: &str, two: &str) -> Self {
+        self.initials = Some(Initials::new(one, two));
+        self
+    }
+
+    /// Keep the high-score table in `path` instead of the user data directory.
+    pub fn high_score_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.high_score_file = Some(HighScoreFile {
+            path: Some(path.into()),
+        });
+        self
+    }
+
+    /// Record every player's input to `path`, saved when the game is over
+    /// or the app exits.
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_path = Some(path.into());
+        self
+    }
+
+    /// Play back a recording made with `record` in place of live input.
+    /// The recording's seed, game mode, difficulty and playfield size are
+    /// used, and the game starts straight away.
+    pub fn replay(mut self, path: impl Into<PathBuf>) -> io::Result<Self> {
+        let replay = Replay::load(path.into())?;
+        self.seed = Some(replay.seed);
+        self.game_mode = replay.game_mode;
+        self.difficulty = replay.difficulty;
+        self.start_playing = true;
+        self.replay = Some(replay);
+        Ok(self)
+    }
+
+    pub fn build(self) -> App {
+        let mut app = App::new();
+
+        match self.headless_playfield {
+            Some(playfield) => {
+                app.add_plugins(MinimalPlugins)
+                    .add_plugin(DiagnosticsPlugin)
+                    .add_plugin(TransformPlugin)
+                    .add_plugin(HierarchyPlugin)
+                    .add_plugin(InputPlugin)
+                    .add_plugin(AssetPlugin::default())
+                    .add_asset::<Image>()
+                    .add_asset::<TextureAtlas>()
+                    .insert_resource(playfield)
+                    .insert_resource(HighScoreFile { path: None })
+                    .init_resource::<HeadlessFrameTime>()
+                    .add_system(
+                        headless_clock_system
+                            .in_base_set(CoreSet::First)
+                            .before(TimeSystem),
+                    );
+            }
+            None => {
+                app.insert_resource(ClearColor(Color::rgb_u8(85, 128, 0)))
+                    .add_plugins(DefaultPlugins.set(WindowPlugin {
+                        primary_window: Some(Window {
+                            mode: WindowMode::BorderlessFullscreen,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }));
+            }
+        }
+
+        if let Some(seed) = self.seed {
+            app.insert_resource(GameRng::new(seed));
+        }
+
+        if let Some(high_score_file) = self.high_score_file {
+            app.insert_resource(high_score_file);
+        }
+
+        if let Some(initials) = self.initials {
+            app.insert_resource(initials);
+        }
+
+        app.insert_resource(self.game_mode)
+            .insert_resource(self.difficulty);
+
+        if let Some(replay) = &self.replay {
+            app.insert_resource(replay.playfield);
+        }
+
+        app.add_plugin(GamePlugin)
+            .add_plugin(UIPlugin)
+            .add_plugin(AudioPlugin)
+            .add_plugin(WorldPlugin)
+            .add_plugin(ScorePlugin)
+            .add_plugin(DifficultyPlugin)
+            .add_plugin(CollisionPlugin)
+            .add_plugin(PlayerPlugin)
+            .add_plugin(EnemyPlugin)
+            .add_plugin(WavePlugin)
+            .add_plugin(ItemPlugin)
+            .add_plugin(WeaponPlugin)
+            .add_plugin(VfxPlugin)
+            .add_plugin(AnimationPlugin)
+            .add_plugin(ReplayPlugin)
+            .add_startup_system(setup_system);
+
+        let seed = app.world.resource::<GameRng>().seed;
+        info!("Game RNG seed: {}", seed);
+
+        if let Some(record_path) = self.record_path {
+            app.insert_resource(ReplayRecorder::new(
+                record_path,
+                seed,
+                self.game_mode,
+                self.difficulty,
+            ));
+        }
+
+        if let Some(replay) = self.replay {
+            app.insert_resource(ReplayPlayback::new(replay))
+                .insert_resource(ToggleActions::<ControlAction>::DISABLED);
+        }
+
+        if self.start_playing {
+            app.world
+                .resource_mut::<NextState<AppState>>()
+                .set(AppState::InGame);
+            app.world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Playing);
+        }
+
+        app
+    }
+}
+
+/// Advance the clock of a headless app by exactly one `HeadlessFrameTime`
+/// per update, however long the update really took.
+fn headless_clock_system(
+    time: Res<Time>,
+    frame_time: Res<HeadlessFrameTime>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    let last_update = time.last_update().unwrap_or_else(|| time.startup());
+    *time_update_strategy = TimeUpdateStrategy::ManualInstant(last_update + frame_time.0);
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// The part of an entity that can be hit. Sizes and `offset` are in sprite
+/// pixels, measured from the centre of the sprite's cell; the entity's
+/// `Transform` scales, mirrors and places them in the world.
+#[derive(Component, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    #[serde(default)]
+    pub offset: Vec2,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ColliderShape {
+    Circle { radius: f32 },
+    Aabb { width: f32, height: f32 },
+}
+
+impl Collider {
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            shape: ColliderShape::Circle { radius },
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn aabb(width: f32, height: f32) -> Self {
+        Self {
+            shape: ColliderShape::Aabb { width, height },
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+}
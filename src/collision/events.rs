@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+/// A fireball overlaps an enemy.
+pub struct FireballHitEnemy {
+    pub fireball: Entity,
+    pub enemy: Entity,
+}
+
+/// An enemy overlaps a player.
+pub struct EnemyHitPlayer {
+    pub enemy: Entity,
+    pub player: Entity,
+}
+
+/// A thrown bone overlaps a player.
+pub struct BoneHitPlayer {
+    pub bone: Entity,
+    pub player: Entity,
+}
+
+/// A player overlaps a power-up.
+pub struct PlayerHitPowerUp {
+    pub player: Entity,
+    pub power_up: Entity,
+}
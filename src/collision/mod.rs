@@ -0,0 +1,60 @@
+pub mod components;
+pub mod events;
+pub mod resources;
+pub mod systems;
+pub mod utils;
+
+use bevy::prelude::*;
+
+use crate::collision::events::{BoneHitPlayer, EnemyHitPlayer, FireballHitEnemy, PlayerHitPowerUp};
+use crate::collision::resources::SpatialGrid;
+use crate::collision::systems::{
+    bone_player_collision_system, enemy_player_collision_system, fireball_enemy_collision_system,
+    player_power_up_collision_system, spatial_grid_update_system,
+};
+use crate::common::TickSet;
+use crate::game::states::GameState;
+use crate::world::WorldSystemSet;
+
+/// Width and height of a spatial grid cell. Around the size of the largest
+/// collider, so most lookups only touch a few cells.
+pub const SPATIAL_GRID_CELL_SIZE: f32 = 128.0;
+
+/// Finds what is touching what at the start of a tick, from where the last
+/// tick left everything, and reports it as collision events for the
+/// gameplay sets to act on.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct CollisionSystemSet;
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialGrid::new(SPATIAL_GRID_CELL_SIZE))
+            .add_event::<FireballHitEnemy>()
+            .add_event::<EnemyHitPlayer>()
+            .add_event::<BoneHitPlayer>()
+            .add_event::<PlayerHitPowerUp>()
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    CollisionSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(WorldSystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
+            .add_systems(
+                (
+                    spatial_grid_update_system,
+                    fireball_enemy_collision_system,
+                    enemy_player_collision_system,
+                    bone_player_collision_system,
+                    player_power_up_collision_system,
+                )
+                    .chain()
+                    .in_set(CollisionSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
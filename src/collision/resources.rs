@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::collision::utils::ColliderBounds;
+
+/// Every collider in the world, bucketed by the grid cells its bounds
+/// cover. Rebuilt at the start of each tick, so collision checks only need
+/// to look at what is nearby.
+#[derive(Resource)]
+pub struct SpatialGrid {
+    pub cell_size: f32,
+    entries: Vec<(Entity, ColliderBounds)>,
+    cells: HashMap<IVec2, Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            entries: Vec::new(),
+            cells: HashMap::default(),
+        }
+    }
+
+    /// Empty the grid, keeping the storage of cells that are still in use.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cells.retain(|_, indices| !indices.is_empty());
+        for indices in self.cells.values_mut() {
+            indices.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, bounds: ColliderBounds) {
+        let index = self.entries.len();
+        self.entries.push((entity, bounds));
+
+        let (min, max) = self.cell_range(&bounds);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// Entities whose colliders overlap `bounds`, in the order they were
+    /// inserted. Entity ids depend on frame timing, so they aren't used for
+    /// ordering; that keeps replays deterministic.
+    pub fn overlapping(&self, bounds: &ColliderBounds) -> Vec<Entity> {
+        let (min, max) = self.cell_range(bounds);
+        let mut overlapping: Vec<usize> = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                if let Some(indices) = self.cells.get(&IVec2::new(x, y)) {
+                    overlapping.extend(
+                        indices
+                            .iter()
+                            .copied()
+                            .filter(|index| bounds.intersects(&self.entries[*index].1)),
+                    );
+                }
+            }
+        }
+
+        // Anything spanning several cells was found once per cell.
+        overlapping.sort_unstable();
+        overlapping.dedup();
+        overlapping
+            .into_iter()
+            .map(|index| self.entries[index].0)
+            .collect()
+    }
+
+    fn cell_range(&self, bounds: &ColliderBounds) -> (IVec2, IVec2) {
+        let rect = bounds.rect();
+        (
+            (rect.min / self.cell_size).floor().as_ivec2(),
+            (rect.max / self.cell_size).floor().as_ivec2(),
+        )
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+use crate::collision::components::Collider;
+use crate::collision::events::{BoneHitPlayer, EnemyHitPlayer, FireballHitEnemy, PlayerHitPowerUp};
+use crate::collision::resources::SpatialGrid;
+use crate::collision::utils::ColliderBounds;
+
+use crate::enemy::components::{Bone, Enemy};
+use crate::item::components::PowerUp;
+use crate::player::components::{Fireball, Player};
+
+pub fn spatial_grid_update_system(
+    mut spatial_grid: ResMut<SpatialGrid>,
+    collider_query: Query<(Entity, &Collider, &Transform)>,
+) {
+    spatial_grid.clear();
+    for (entity, collider, transform) in collider_query.iter() {
+        spatial_grid.insert(entity, ColliderBounds::new(collider, transform));
+    }
+}
+
+pub fn fireball_enemy_collision_system(
+    spatial_grid: Res<SpatialGrid>,
+    fireball_query: Query<(Entity, &Collider, &Transform), With<Fireball>>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut collision_events: EventWriter<FireballHitEnemy>,
+) {
+    for (fireball_entity, fireball_collider, fireball_transform) in fireball_query.iter() {
+        let fireball_bounds = ColliderBounds::new(fireball_collider, fireball_transform);
+        for entity in spatial_grid.overlapping(&fireball_bounds) {
+            if enemy_query.contains(entity) {
+                collision_events.send(FireballHitEnemy {
+                    fireball: fireball_entity,
+                    enemy: entity,
+                });
+            }
+        }
+    }
+}
+
+pub fn enemy_player_collision_system(
+    spatial_grid: Res<SpatialGrid>,
+    player_query: Query<(Entity, &Collider, &Transform), With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut collision_events: EventWriter<EnemyHitPlayer>,
+) {
+    for (player_entity, player_collider, player_transform) in player_query.iter() {
+        let player_bounds = ColliderBounds::new(player_collider, player_transform);
+        for entity in spatial_grid.overlapping(&player_bounds) {
+            if enemy_query.contains(entity) {
+                collision_events.send(EnemyHitPlayer {
+                    enemy: entity,
+                    player: player_entity,
+                });
+            }
+        }
+    }
+}
+
+pub fn bone_player_collision_system(
+    spatial_grid: Res<SpatialGrid>,
+    bone_query: Query<(Entity, &Collider, &Transform), With<Bone>>,
+    player_query: Query<(), With<Player>>,
+    mut collision_events: EventWriter<BoneHitPlayer>,
+) {
+    for (bone_entity, bone_collider, bone_transform) in bone_query.iter() {
+        let bone_bounds = ColliderBounds::new(bone_collider, bone_transform);
+        for entity in spatial_grid.overlapping(&bone_bounds) {
+            if player_query.contains(entity) {
+                collision_events.send(BoneHitPlayer {
+                    bone: bone_entity,
+                    player: entity,
+                });
+            }
+        }
+    }
+}
+
+pub fn player_power_up_collision_system(
+    spatial_grid: Res<SpatialGrid>,
+    power_up_query: Query<(Entity, &Collider, &Transform), With<PowerUp>>,
+    player_query: Query<(), With<Player>>,
+    mut collision_events: EventWriter<PlayerHitPowerUp>,
+) {
+    for (power_up_entity, power_up_collider, power_up_transform) in power_up_query.iter() {
+        let power_up_bounds = ColliderBounds::new(power_up_collider, power_up_transform);
+        for entity in spatial_grid.overlapping(&power_up_bounds) {
+            if player_query.contains(entity) {
+                collision_events.send(PlayerHitPowerUp {
+                    player: entity,
+                    power_up: power_up_entity,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::collision::components::{Collider, ColliderShape};
+
+/// A collider placed in the world.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColliderBounds {
+    Circle { center: Vec2, radius: f32 },
+    Aabb { center: Vec2, half_size: Vec2 },
+}
+
+impl ColliderBounds {
+    pub fn new(collider: &Collider, transform: &Transform) -> Self {
+        let center = transform
+            .transform_point(collider.offset.extend(0.0))
+            .truncate();
+        let scale = transform.scale.truncate().abs();
+
+        match collider.shape {
+            ColliderShape::Circle { radius } => ColliderBounds::Circle {
+                center,
+                radius: radius * scale.max_element(),
+            },
+            ColliderShape::Aabb { width, height } => ColliderBounds::Aabb {
+                center,
+                half_size: Vec2::new(width, height) * scale / 2.0,
+            },
+        }
+    }
+
+    /// The smallest axis-aligned rectangle containing the collider.
+    pub fn rect(&self) -> Rect {
+        match *self {
+            ColliderBounds::Circle { center, radius } => {
+                Rect::from_center_half_size(center, Vec2::splat(radius))
+            }
+            ColliderBounds::Aabb { center, half_size } => {
+                Rect::from_center_half_size(center, half_size)
+            }
+        }
+    }
+
+    pub fn intersects(&self, other: &ColliderBounds) -> bool {
+        match (*self, *other) {
+            (
+                ColliderBounds::Circle {
+                    center: a,
+                    radius: a_radius,
+                },
+                ColliderBounds::Circle {
+                    center: b,
+                    radius: b_radius,
+                },
+            ) => a.distance_squared(b) < (a_radius + b_radius).powi(2),
+            (
+                ColliderBounds::Aabb {
+                    center: a,
+                    half_size: a_half_size,
+                },
+                ColliderBounds::Aabb {
+                    center: b,
+                    half_size: b_half_size,
+                },
+            ) => {
+                let gap = (a - b).abs() - (a_half_size + b_half_size);
+                gap.x < 0.0 && gap.y < 0.0
+            }
+            (
+                ColliderBounds::Circle { center, radius },
+                ColliderBounds::Aabb {
+                    center: box_center,
+                    half_size,
+                },
+            )
+            | (
+                ColliderBounds::Aabb {
+                    center: box_center,
+                    half_size,
+                },
+                ColliderBounds::Circle { center, radius },
+            ) => {
+                let closest = center.clamp(box_center - half_size, box_center + half_size);
+                center.distance_squared(closest) < radius * radius
+            }
+        }
+    }
+}
+
+/// Whether two entities' colliders overlap.
+pub fn colliding(a: (&Collider, &Transform), b: (&Collider, &Transform)) -> bool {
+    ColliderBounds::new(a.0, a.1).intersects(&ColliderBounds::new(b.0, b.1))
+}
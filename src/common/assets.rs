@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::Resource;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::collision::components::Collider;
+use crate::common::resources::SpriteSheets;
+use crate::common::{REQUIRED_SPRITES, SPRITE_MANIFEST};
+
+/// A RON file in `assets/` that is loaded as an asset of its own type.
+pub trait RonManifest: TypeUuid + DeserializeOwned + Send + Sync + 'static {
+    /// Where the manifest is, relative to `assets/`.
+    const PATH: &'static str;
+    /// The endings `RonManifestLoader` picks the file out by, e.g.
+    /// `"weapons.ron"`.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// A manifest the game reads through a resource built from it, such as
+/// `WeaponTypes` from the weapon manifest.
+pub trait ManifestResource: RonManifest {
+    type Resource: Resource;
+
+    /// Check the manifest and build its resource. An `Err` says what is
+    /// wrong with it; the manifest is then ignored, and whatever was built
+    /// from the last good one stays in use.
+    fn build(&self) -> Result<Self::Resource, String>;
+}
+
+/// Loads any `RonManifest`.
+pub struct RonManifestLoader<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonManifestLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: RonManifest> AssetLoader for RonManifestLoader<T> {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let manifest: T = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(manifest));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        T::EXTENSIONS
+    }
+}
+
+/// Every sprite sheet in the game, keyed by name. Loaded from
+/// `assets/manifest.sprites.ron`, so sprites can be added or retuned without
+/// recompiling.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "dbeaa777-2d4b-4f06-af9b-e7c0e9af36ac"]
+pub struct SpriteManifest {
+    pub sheets: HashMap<String, SpriteSheet>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpriteSheet {
+    pub file: String,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    #[serde(default = "default_cells")]
+    pub columns: usize,
+    #[serde(default = "default_cells")]
+    pub rows: usize,
+    /// Mirror the sheet horizontally, for art drawn facing the wrong way.
+    #[serde(default)]
+    pub flip_x: bool,
+    #[serde(default)]
+    pub animations: HashMap<String, SpriteAnimation>,
+    /// Hitbox of entities drawn with this sheet, the whole cell if unset.
+    #[serde(default)]
+    pub collider: Option<Collider>,
+}
+
+/// A named run of cells in a sprite sheet.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct SpriteAnimation {
+    pub first: usize,
+    pub last: usize,
+    /// Seconds each cell is shown for, `DEFAULT_FRAME_TIME` if unset.
+    #[serde(default)]
+    pub frame_time: Option<f32>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+fn default_cells() -> usize {
+    1
+}
+
+impl SpriteSheet {
+    /// What, if anything, stops the sheet from being cut into an atlas and
+    /// animated.
+    fn check(&self) -> Result<(), String> {
+        if self.columns == 0 || self.rows == 0 {
+            return Err(format!(
+                "has {} columns and {} rows",
+                self.columns, self.rows
+            ));
+        }
+        for (name, animation) in self.animations.iter() {
+            if animation.first > animation.last || animation.last >= self.columns * self.rows {
+                return Err(format!(
+                    "animation \"{}\" runs from cell {} to {} of {}",
+                    name,
+                    animation.first,
+                    animation.last,
+                    self.columns * self.rows
+                ));
+            }
+            if let Some(frame_time) = animation.frame_time {
+                if frame_time <= 0.0 || Duration::try_from_secs_f32(frame_time).is_err() {
+                    return Err(format!(
+                        "animation \"{}\" has a frame_time of {}",
+                        name, frame_time
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RonManifest for SpriteManifest {
+    const PATH: &'static str = SPRITE_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["sprites.ron"];
+}
+
+impl ManifestResource for SpriteManifest {
+    type Resource = SpriteSheets;
+
+    fn build(&self) -> Result<SpriteSheets, String> {
+        for (key, sheet) in self.sheets.iter() {
+            sheet
+                .check()
+                .map_err(|problem| format!("sheet \"{}\" {}", key, problem))?;
+        }
+        for (key, animations) in REQUIRED_SPRITES {
+            let sheet = self
+                .sheets
+                .get(*key)
+                .ok_or_else(|| format!("has no sheet \"{}\"", key))?;
+            if let Some(name) = animations
+                .iter()
+                .find(|name| !sheet.animations.contains_key(**name))
+            {
+                return Err(format!("sheet \"{}\" has no animation \"{}\"", key, name));
+            }
+        }
+
+        Ok(SpriteSheets {
+            sheets: self.sheets.clone(),
+        })
+    }
+}
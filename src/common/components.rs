@@ -1,5 +1,4 @@
 use bevy::prelude::*;
-use bevy::time::Timer;
 
 #[derive(Component, Debug, PartialEq, Eq, Default)]
 pub enum Vitality {
@@ -8,6 +7,33 @@ pub enum Vitality {
     Dead,
 }
 
+/// Hit points. An entity with `Health` survives until they run out.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Take `amount` hit points away. Returns true once none are left.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.current = self.current.saturating_sub(amount);
+        self.current == 0
+    }
+}
+
+/// What a projectile does to whatever it hits.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Damage {
+    pub amount: u32,
+    /// How hard the target is pushed back, in pixels per second.
+    pub knockback: f32,
+}
+
 #[derive(Component)]
 pub struct Velocity {
     pub x: f32,
@@ -20,37 +46,15 @@ impl Default for Velocity {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 pub struct Movable {
     pub auto_despawn: bool,
 }
 
-impl Default for Movable {
-    fn default() -> Self {
-        Self {
-            auto_despawn: false,
-        }
-    }
-}
-
-#[derive(Component, Clone, Copy)]
-pub struct AnimationIndices {
-    pub first: usize,
-    pub last: usize,
-}
-
-#[derive(Component, Deref, DerefMut)]
-pub struct AnimationTimer(pub Timer);
-
-impl Default for AnimationTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(0.1, TimerMode::Repeating))
-    }
-}
-
-#[derive(Component)]
-pub struct EntityLocation {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+/// Translation of an entity at the last two simulation ticks, used to
+/// draw it smoothly in between fixed updates.
+#[derive(Component, Default)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Option<Vec3>,
 }
@@ -1,18 +1,66 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+pub mod assets;
 pub mod components;
 pub mod resources;
+pub mod systems;
 pub mod utils;
 
+use crate::common::assets::{ManifestResource, RonManifestLoader};
+use crate::common::systems::{manifest_load_system, manifest_resource_system};
+use crate::weapon::FALLBACK_PROJECTILE;
+
 pub const TIME_STEP: f32 = 1.0 / 60.0;
 pub const BASE_SPEED: f32 = 500.0;
 pub const SCROLL_X_VELOCITY: f32 = 0.1;
 pub const SCROLL_Y_VELOCITY: f32 = 0.1;
 pub const DEFAULT_SPAWN_TIMER: f32 = 2.0;
+pub const SPRITE_MANIFEST: &str = "manifest.sprites.ron";
+/// Sheets the code draws by name, with the animations it plays on them. A
+/// sprite manifest without all of them is not loaded.
+pub const REQUIRED_SPRITES: &[(&str, &[&str])] = &[
+    ("bullet", &["bone", FALLBACK_PROJECTILE]),
+    ("explosion", &["explode"]),
+    ("grass", &[]),
+    ("grass_tuft", &[]),
+    ("tree", &[]),
+    ("player_one", &["idle", "run"]),
+    ("player_one_idle", &["idle"]),
+    ("player_one_shoot", &["shoot"]),
+    ("player_one_ghost", &["float"]),
+    ("player_one_static", &[]),
+    ("player_two", &["idle", "run", "shoot"]),
+    ("player_two_ghost", &["float"]),
+    ("player_two_static", &[]),
+    ("player_diamond", &["run"]),
+    ("player_dead", &["hurt", "die"]),
+];
+
+/// Systems that open and close a fixed simulation tick. Each module's
+/// gameplay system set runs in `CoreSchedule::FixedUpdate` between the two.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum TickSet {
+    Begin,
+    End,
+}
+
+/// Loads the manifest `T` at startup and keeps its resource up to date with
+/// the file.
+pub struct ManifestPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for ManifestPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
 
-pub struct SpriteSheet<'a> {
-    pub file: &'a str,
-    pub width: f32,
-    pub height: f32,
-    pub scale: f32,
-    pub columns: usize,
-    pub rows: usize,
+impl<T: ManifestResource> Plugin for ManifestPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<T>()
+            .init_asset_loader::<RonManifestLoader<T>>()
+            .add_startup_system(manifest_load_system::<T>)
+            .add_system(manifest_resource_system::<T>.in_base_set(CoreSet::First));
+    }
 }
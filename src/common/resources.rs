@@ -1,28 +1,118 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bevy::prelude::*;
 
 use bevy_kira_audio::AudioSource;
 
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::animation::components::AnimationClip;
+use crate::animation::DEFAULT_FRAME_TIME;
+use crate::collision::components::Collider;
+use crate::common::assets::{RonManifest, SpriteAnimation, SpriteSheet};
+use crate::common::SPRITE_MANIFEST;
+
+/// The sprite manifest's sheets, once they have passed
+/// `ManifestResource::build`. `SpriteRegistry` is built from them.
+#[derive(Resource)]
+pub struct SpriteSheets {
+    pub sheets: HashMap<String, SpriteSheet>,
+}
+
+/// The loaded sprite manifest. Exists once `assets/manifest.sprites.ron` has
+/// loaded; the simulation and anything that spawns sprites waits for it.
 #[derive(Resource, Default)]
-pub struct GameTextures {
-    pub player_one: Handle<TextureAtlas>,
-    pub player_two: Handle<TextureAtlas>,
-    pub player_one_dead: Handle<TextureAtlas>,
-    pub player_two_dead: Handle<TextureAtlas>,
-    pub player_one_ghost: Handle<TextureAtlas>,
-    pub player_two_ghost: Handle<TextureAtlas>,
-    pub player_one_static: Handle<Image>,
-    pub player_two_static: Handle<Image>,
-    pub player_diamond: Handle<TextureAtlas>,
-    pub enemy_zombie: Handle<TextureAtlas>,
-    pub enemy_zombie_dead: Handle<TextureAtlas>,
-    pub enemy_skeleton: Handle<TextureAtlas>,
-    pub enemy_skeleton_dead: Handle<TextureAtlas>,
-    pub enemy_goblin: Handle<TextureAtlas>,
-    pub enemy_goblin_dead: Handle<TextureAtlas>,
-    pub bullet: Handle<TextureAtlas>,
-    pub diamond: Handle<TextureAtlas>,
+pub struct SpriteRegistry {
+    pub sheets: HashMap<String, RegisteredSprite>,
+}
+
+impl SpriteRegistry {
+    /// The sheet registered as `key`. Panics if the manifest has no such
+    /// sheet, since the game can't be drawn without it.
+    pub fn get(&self, key: &str) -> &RegisteredSprite {
+        self.sheets
+            .get(key)
+            .unwrap_or_else(|| panic!("no sprite sheet named \"{}\" in {}", key, SPRITE_MANIFEST))
+    }
+
+    /// What, if anything, stops `animations` of the `key` sheet from being
+    /// played, for manifests that name sheets of their own.
+    pub fn check(&self, key: &str, animations: &[&str]) -> Result<(), String> {
+        let Some(registered) = self.sheets.get(key) else {
+            return Err(format!(
+                "is drawn with \"{}\", which isn't a sprite sheet",
+                key
+            ));
+        };
+        match animations
+            .iter()
+            .find(|name| !registered.sheet.animations.contains_key(**name))
+        {
+            Some(name) => Err(format!(
+                "plays \"{}\", which sprite sheet \"{}\" doesn't have",
+                name, key
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A manifest sheet together with its loaded texture and atlas.
+pub struct RegisteredSprite {
+    pub sheet: SpriteSheet,
+    pub image: Handle<Image>,
+    pub atlas: Handle<TextureAtlas>,
+}
+
+impl RegisteredSprite {
+    pub fn atlas(&self) -> Handle<TextureAtlas> {
+        self.atlas.clone()
+    }
+
+    /// The named animation. Panics if the sheet doesn't define it.
+    pub fn animation(&self, name: &str) -> SpriteAnimation {
+        *self.sheet.animations.get(name).unwrap_or_else(|| {
+            panic!(
+                "sprite sheet \"{}\" has no animation named \"{}\"",
+                self.sheet.file, name
+            )
+        })
+    }
+
+    /// The named animation as a looping clip. Panics if the sheet doesn't
+    /// define it.
+    pub fn clip(&self, name: &str) -> AnimationClip {
+        let animation = self.animation(name);
+        AnimationClip {
+            atlas: self.atlas(),
+            first: animation.first,
+            last: animation.last,
+            frame_time: Duration::from_secs_f32(animation.frame_time.unwrap_or(DEFAULT_FRAME_TIME)),
+            looping: true,
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        self.sheet
+            .collider
+            .unwrap_or_else(|| Collider::aabb(self.sheet.width, self.sheet.height))
+    }
+
+    pub fn rotation(&self) -> Quat {
+        if self.sheet.flip_x {
+            Quat::from_rotation_y(std::f32::consts::PI)
+        } else {
+            Quat::IDENTITY
+        }
+    }
 }
 
+/// Keeps a manifest loaded.
+#[derive(Resource)]
+pub struct ManifestHandle<T: RonManifest>(pub Handle<T>);
+
 #[derive(Resource)]
 pub struct GameAudio {
     pub player_dead: Handle<AudioSource>,
@@ -31,6 +121,77 @@ pub struct GameAudio {
     pub diamond_powerup: Handle<AudioSource>,
 }
 
+/// The area the game is played in, centred on the origin.
+/// Follows the primary window by default; a playfield made with
+/// `Playfield::new` keeps its size (e.g. when running headless or
+/// playing back a replay).
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct Playfield {
+    pub width: f32,
+    pub height: f32,
+    pub follow_window: bool,
+}
+
+impl Default for Playfield {
+    fn default() -> Self {
+        Self {
+            width: 1280.0,
+            height: 720.0,
+            follow_window: true,
+        }
+    }
+}
+
+impl Playfield {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            follow_window: false,
+        }
+    }
+}
+
+/// Seeded source of all gameplay randomness.
+/// Each subsystem draws from its own stream, so spawning one more decoration
+/// does not shift the enemy layout for the rest of the run.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub enemies: ChaCha8Rng,
+    pub enemy_ai: ChaCha8Rng,
+    pub items: ChaCha8Rng,
+    pub world: ChaCha8Rng,
+    pub loot: ChaCha8Rng,
+    pub ground: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        let stream = |stream: u64| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(stream);
+            rng
+        };
+
+        Self {
+            seed,
+            enemies: stream(0),
+            enemy_ai: stream(1),
+            items: stream(2),
+            world: stream(3),
+            loot: stream(4),
+            ground: stream(5),
+        }
+    }
+}
+
 // #[derive(Resource)]
 // pub struct TimerRepeating {
 //     pub timer: Timer,
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy::sprite::TextureAtlas;
+
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::common::components::Interpolated;
+use crate::common::resources::{
+    GameAudio, ManifestHandle, RegisteredSprite, SpriteRegistry, SpriteSheets,
+};
+use crate::common::utils::{get_game_sound, get_texture_atlas};
+use crate::common::SPRITE_MANIFEST;
+
+pub fn setup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // Game Sounds
+    let player_dead_sound = get_game_sound("dead.ogg", &asset_server);
+    let player_shoot_sound = get_game_sound("shoot.ogg", &asset_server);
+    let enemy_dead_sound = get_game_sound("zombie-die.ogg", &asset_server);
+    let diamond_powerup_sound = get_game_sound("diamond-powerup.ogg", &asset_server);
+
+    let game_sounds = GameAudio {
+        player_dead: player_dead_sound,
+        player_shoot: player_shoot_sound,
+        enemy_dead: enemy_dead_sound,
+        diamond_powerup: diamond_powerup_sound,
+    };
+    commands.insert_resource(game_sounds);
+}
+
+pub fn manifest_load_system<T: RonManifest>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(ManifestHandle::<T>(asset_server.load(T::PATH)));
+}
+
+/// Build the manifest's resource once it has loaded, and build it again
+/// whenever the manifest changes on disk. A manifest that doesn't pass
+/// `ManifestResource::build` is ignored with a warning.
+pub fn manifest_resource_system<T: ManifestResource>(
+    mut commands: Commands,
+    mut manifest_events: EventReader<AssetEvent<T>>,
+    manifests: Res<Assets<T>>,
+    manifest_handle: Res<ManifestHandle<T>>,
+) {
+    for manifest_event in manifest_events.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = manifest_event
+        else {
+            continue;
+        };
+        if *handle != manifest_handle.0 {
+            continue;
+        }
+        let Some(manifest) = manifests.get(handle) else {
+            continue;
+        };
+
+        match manifest.build() {
+            Ok(resource) => {
+                info!("Loaded {}", T::PATH);
+                commands.insert_resource(resource);
+            }
+            Err(problem) => warn!("{}: {}, ignoring it", T::PATH, problem),
+        }
+    }
+}
+
+/// Build the `SpriteRegistry` once the sprite sheets have loaded, and
+/// refresh it in place whenever they change. A sprite manifest that fails
+/// its checks never gets this far, so the registry in use is kept.
+pub fn sprite_registry_system(
+    mut commands: Commands,
+    sprite_sheets: Res<SpriteSheets>,
+    mut sprite_registry: Option<ResMut<SpriteRegistry>>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    if !sprite_sheets.is_changed() {
+        return;
+    }
+
+    let mut sheets = HashMap::new();
+    for (key, sheet) in sprite_sheets.sheets.iter() {
+        let image: Handle<Image> = asset_server.load(sheet.file.as_str());
+        let texture_atlas = get_texture_atlas(sheet, image.clone());
+
+        // Reuse the atlas handle of a sheet that is already in use, so
+        // sprites on screen pick up the change.
+        let existing_atlas = sprite_registry
+            .as_ref()
+            .and_then(|sprite_registry| sprite_registry.sheets.get(key))
+            .map(|registered| registered.atlas.clone());
+        let atlas = match existing_atlas {
+            Some(atlas) => {
+                texture_atlases.set_untracked(atlas.clone(), texture_atlas);
+                atlas
+            }
+            None => texture_atlases.add(texture_atlas),
+        };
+
+        sheets.insert(
+            key.clone(),
+            RegisteredSprite {
+                sheet: sheet.clone(),
+                image,
+                atlas,
+            },
+        );
+    }
+
+    info!(
+        "Loaded {} sprite sheets from {}",
+        sheets.len(),
+        SPRITE_MANIFEST
+    );
+
+    match sprite_registry.as_mut() {
+        Some(sprite_registry) => sprite_registry.sheets = sheets,
+        None => commands.insert_resource(SpriteRegistry { sheets }),
+    }
+}
+
+/// Put interpolated entities back at their simulated position before a tick.
+pub fn interpolation_restore_system(mut query: Query<(&mut Transform, &Interpolated)>) {
+    for (mut transform, interpolated) in query.iter_mut() {
+        if let Some(current) = interpolated.current {
+            transform.translation = current;
+        }
+    }
+}
+
+/// Remember where the tick left each interpolated entity.
+pub fn interpolation_capture_system(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = interpolated.current.unwrap_or(transform.translation);
+        interpolated.current = Some(transform.translation);
+    }
+}
+
+/// Draw interpolated entities part way between their last two ticks.
+pub fn interpolation_render_system(
+    mut query: Query<(&mut Transform, &Interpolated)>,
+    fixed_time: Res<FixedTime>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / fixed_time.period.as_secs_f32()).min(1.0);
+
+    for (mut transform, interpolated) in query.iter_mut() {
+        if let Some(current) = interpolated.current {
+            transform.translation = interpolated.previous.lerp(current, alpha);
+        }
+    }
+}
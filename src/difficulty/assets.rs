@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::difficulty::resources::{DifficultyPreset, DifficultyPresets};
+use crate::difficulty::DIFFICULTY_MANIFEST;
+
+/// How each difficulty preset ramps up over a match. Loaded from
+/// `assets/manifest.difficulty.ron`.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "a3c47e21-8b5d-4f6a-9e02-7d1b3c5f8e64"]
+pub struct DifficultyManifest {
+    pub presets: BTreeMap<DifficultyPreset, DifficultyCurves>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct DifficultyCurves {
+    /// Multiplies how many enemies a wave brings in, and divides the
+    /// delays and pauses between them.
+    pub spawn_rate: DifficultyCurve,
+    /// Multiplies enemy speeds.
+    pub enemy_speed: DifficultyCurve,
+    /// Multiplies enemy hit points, rounded.
+    pub enemy_health: DifficultyCurve,
+    /// Chance, from 0 to 1, that a new enemy is an elite.
+    pub elite_chance: DifficultyCurve,
+}
+
+/// A value that grows with the minutes played and the points the players
+/// have scored together, up to `max`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyCurve {
+    pub start: f32,
+    #[serde(default)]
+    pub per_minute: f32,
+    #[serde(default)]
+    pub per_point: f32,
+    pub max: f32,
+}
+
+impl DifficultyCurves {
+    /// What, if anything, stops the curves from being followed.
+    fn check(&self) -> Result<(), String> {
+        let curves = [
+            ("spawn_rate", self.spawn_rate),
+            ("enemy_speed", self.enemy_speed),
+            ("enemy_health", self.enemy_health),
+            ("elite_chance", self.elite_chance),
+        ];
+        for (name, curve) in curves {
+            let DifficultyCurve {
+                start,
+                per_minute,
+                per_point,
+                max,
+            } = curve;
+            if ![start, per_minute, per_point, max]
+                .iter()
+                .all(|value| value.is_finite())
+            {
+                return Err(format!("has a {} curve that isn't finite", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DifficultyCurve {
+    pub fn value(&self, minutes: f32, score: u32) -> f32 {
+        let value = self.start + self.per_minute * minutes + self.per_point * score as f32;
+        value.min(self.max)
+    }
+}
+
+impl RonManifest for DifficultyManifest {
+    const PATH: &'static str = DIFFICULTY_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["difficulty.ron"];
+}
+
+impl ManifestResource for DifficultyManifest {
+    type Resource = DifficultyPresets;
+
+    fn build(&self) -> Result<DifficultyPresets, String> {
+        for preset in DifficultyPreset::ALL {
+            let Some(curves) = self.presets.get(&preset) else {
+                return Err(format!("no {} difficulty", preset.label()));
+            };
+            curves
+                .check()
+                .map_err(|problem| format!("{} difficulty {}", preset.label(), problem))?;
+        }
+
+        Ok(DifficultyPresets {
+            presets: self.presets.clone(),
+        })
+    }
+}
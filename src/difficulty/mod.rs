@@ -0,0 +1,44 @@
+pub mod assets;
+pub mod resources;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::common::ManifestPlugin;
+use crate::common::TickSet;
+use crate::difficulty::assets::DifficultyManifest;
+use crate::difficulty::resources::{Difficulty, DifficultyPreset};
+use crate::difficulty::systems::difficulty_update_system;
+use crate::game::states::GameState;
+use crate::world::WorldSystemSet;
+
+pub const DIFFICULTY_MANIFEST: &str = "manifest.difficulty.ron";
+
+/// Works out the difficulty for the tick, before anything is spawned or
+/// moved with it.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct DifficultySystemSet;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultyPreset>()
+            .init_resource::<Difficulty>()
+            .add_plugin(ManifestPlugin::<DifficultyManifest>::default())
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    DifficultySystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(TickSet::Begin)
+                        .before(WorldSystemSet)
+                        .before(TickSet::End),
+                );
+            })
+            .add_system(
+                difficulty_update_system
+                    .in_set(DifficultySystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
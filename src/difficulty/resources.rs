@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::assets::DifficultyCurves;
+use crate::difficulty::DIFFICULTY_MANIFEST;
+
+/// Which set of difficulty curves a game is played on.
+#[derive(
+    Resource,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl DifficultyPreset {
+    pub const ALL: [DifficultyPreset; 3] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Normal => "Normal",
+            DifficultyPreset::Hard => "Hard",
+        }
+    }
+}
+
+/// The loaded difficulty manifest. Exists once
+/// `assets/manifest.difficulty.ron` has loaded.
+#[derive(Resource, Default)]
+pub struct DifficultyPresets {
+    pub presets: BTreeMap<DifficultyPreset, DifficultyCurves>,
+}
+
+impl DifficultyPresets {
+    /// The curves for `preset`. The manifest is only loaded with every
+    /// preset, so this only panics before it has loaded.
+    pub fn get(&self, preset: DifficultyPreset) -> &DifficultyCurves {
+        self.presets.get(&preset).unwrap_or_else(|| {
+            panic!(
+                "no {} difficulty in {}",
+                preset.label(),
+                DIFFICULTY_MANIFEST
+            )
+        })
+    }
+}
+
+/// How hard the game is right now, worked out each tick from the preset's
+/// curves. Starts out at no change to the enemy manifest.
+#[derive(Resource, Debug, Clone)]
+pub struct Difficulty {
+    /// Time spent playing this match.
+    pub elapsed: Duration,
+    pub spawn_rate: f32,
+    pub enemy_speed: f32,
+    pub enemy_health: f32,
+    pub elite_chance: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            spawn_rate: 1.0,
+            enemy_speed: 1.0,
+            enemy_health: 1.0,
+            elite_chance: 0.0,
+        }
+    }
+}
+
+impl Difficulty {
+    /// `seconds` shortened to match the spawn rate.
+    pub fn spawn_delay(&self, seconds: f32) -> f32 {
+        seconds / self.spawn_rate
+    }
+
+    /// `count` enemies scaled by the spawn rate, never fewer than one.
+    pub fn spawn_count(&self, count: u32) -> u32 {
+        ((count as f32 * self.spawn_rate).round() as u32).max(1)
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::difficulty::resources::{Difficulty, DifficultyPreset, DifficultyPresets};
+use crate::player::components::{Player, Score};
+
+/// Work out this tick's difficulty from the time played and the players'
+/// combined score.
+pub fn difficulty_update_system(
+    mut difficulty: ResMut<Difficulty>,
+    difficulty_preset: Res<DifficultyPreset>,
+    difficulty_presets: Res<DifficultyPresets>,
+    player_query: Query<&Score, With<Player>>,
+    fixed_time: Res<FixedTime>,
+) {
+    difficulty.elapsed += fixed_time.period;
+
+    let curves = difficulty_presets.get(*difficulty_preset);
+    let minutes = difficulty.elapsed.as_secs_f32() / 60.0;
+    let score = player_query.iter().map(|score| score.value).sum();
+
+    // keep a badly tuned manifest from stopping the waves or the enemies, or
+    // panicking the rng
+    difficulty.spawn_rate = curves.spawn_rate.value(minutes, score).max(0.1);
+    difficulty.enemy_speed = curves.enemy_speed.value(minutes, score).max(0.1);
+    difficulty.enemy_health = curves.enemy_health.value(minutes, score);
+    difficulty.elite_chance = curves.elite_chance.value(minutes, score).clamp(0.0, 1.0);
+}
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::warn;
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::collision::components::Collider;
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::common::resources::SpriteRegistry;
+use crate::enemy::boss::assets::BossType;
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::ENEMY_MANIFEST;
+
+/// Every kind of enemy, keyed by name. Loaded from
+/// `assets/manifest.enemies.ron`, so new enemies need no code changes.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "fbb968b6-1167-47be-8d8b-4529f3ab253c"]
+pub struct EnemyManifest {
+    pub enemies: BTreeMap<String, EnemyType>,
+    #[serde(default)]
+    pub boss: Option<BossType>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyType {
+    /// `SpriteRegistry` key of the sheet drawn while alive.
+    pub sprite: String,
+    #[serde(default = "default_walk_animation")]
+    pub walk_animation: String,
+    /// `SpriteRegistry` key of the sheet drawn once killed.
+    pub death_sprite: String,
+    #[serde(default = "default_death_animation")]
+    pub death_animation: String,
+    /// Animation of the `death_sprite` sheet held while hurt.
+    #[serde(default = "default_hurt_animation")]
+    pub hurt_animation: String,
+    /// Range each axis of the enemy's velocity is picked from.
+    pub speed: SpeedRange,
+    /// Hit points, so how much damage it takes to kill the enemy.
+    pub health: u32,
+    /// Points for shooting the enemy.
+    pub score: u32,
+    /// How likely the enemy is to be picked, relative to the others.
+    pub spawn_weight: f32,
+    /// Hitbox, in pixels of the `sprite` sheet.
+    pub collider: Collider,
+    /// How the enemy moves and attacks.
+    pub behaviour: EnemyBehaviour,
+    /// What the enemy may leave behind when killed.
+    #[serde(default)]
+    pub loot: Vec<LootEntry>,
+}
+
+/// One item of a loot table, rolled for on its own each kill.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LootEntry {
+    /// `ItemTypes` key of the item dropped.
+    pub item: String,
+    /// Odds of it dropping, from 0 to 1.
+    pub chance: f64,
+}
+
+/// Each behaviour is driven by its own system, keyed on the component the
+/// enemy is spawned with.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum EnemyBehaviour {
+    /// Walk left, drifting toward the nearest player's height on some ticks.
+    /// `homing` scales how fast it drifts. See `Shambler`.
+    Shamble { homing: f32 },
+    /// Stop `distance` pixels short of the nearest player and throw `bones`
+    /// bones at them, one every `throw_interval` seconds, then walk on.
+    /// See `Skirmisher`.
+    KeepDistance {
+        distance: f32,
+        throw_interval: f32,
+        bone_speed: f32,
+        bones: u32,
+    },
+    /// Walk left until within `range` pixels of a player, stand still for
+    /// `wind_up` seconds, then charge at where they were. See `Charger`.
+    Charge {
+        range: f32,
+        wind_up: f32,
+        speed: f32,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct SpeedRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl EnemyType {
+    /// What, if anything, stops the enemy from being spawned.
+    fn check(&self) -> Result<(), String> {
+        if !self.spawn_weight.is_finite() || self.spawn_weight < 0.0 {
+            return Err(format!("has a spawn_weight of {}", self.spawn_weight));
+        }
+        if !(self.speed.min.is_finite() && self.speed.max.is_finite())
+            || self.speed.min > self.speed.max
+        {
+            return Err(format!(
+                "has a speed range of {} to {}",
+                self.speed.min, self.speed.max
+            ));
+        }
+        for loot_entry in self.loot.iter() {
+            if !(0.0..=1.0).contains(&loot_entry.chance) {
+                return Err(format!(
+                    "drops \"{}\" with a chance of {}",
+                    loot_entry.item, loot_entry.chance
+                ));
+            }
+        }
+        self.behaviour.check()
+    }
+
+    /// What, if anything, stops the enemy from being drawn with the sheets
+    /// in `sprite_registry`.
+    pub fn check_sprites(&self, sprite_registry: &SpriteRegistry) -> Result<(), String> {
+        sprite_registry.check(&self.sprite, &[&self.walk_animation])?;
+        sprite_registry.check(
+            &self.death_sprite,
+            &[&self.hurt_animation, &self.death_animation],
+        )
+    }
+}
+
+impl EnemyBehaviour {
+    /// What, if anything, stops the behaviour from being driven.
+    fn check(&self) -> Result<(), String> {
+        match *self {
+            EnemyBehaviour::Shamble { homing } if !homing.is_finite() => {
+                Err(format!("has a homing of {}", homing))
+            }
+            EnemyBehaviour::KeepDistance {
+                distance,
+                throw_interval,
+                bone_speed,
+                ..
+            } => {
+                if !throw_interval.is_finite() || throw_interval <= 0.0 {
+                    return Err(format!("has a throw_interval of {}", throw_interval));
+                }
+                if !(distance.is_finite() && bone_speed.is_finite()) {
+                    return Err(format!(
+                        "has a distance of {} and a bone_speed of {}",
+                        distance, bone_speed
+                    ));
+                }
+                Ok(())
+            }
+            EnemyBehaviour::Charge {
+                range,
+                wind_up,
+                speed,
+            } => {
+                if !wind_up.is_finite() || wind_up < 0.0 {
+                    return Err(format!("has a wind_up of {}", wind_up));
+                }
+                if !(range.is_finite() && speed.is_finite()) {
+                    return Err(format!("has a range of {} and a speed of {}", range, speed));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_walk_animation() -> String {
+    "walk".to_string()
+}
+
+fn default_death_animation() -> String {
+    "die".to_string()
+}
+
+fn default_hurt_animation() -> String {
+    "hurt".to_string()
+}
+
+impl RonManifest for EnemyManifest {
+    const PATH: &'static str = ENEMY_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["enemies.ron"];
+}
+
+impl ManifestResource for EnemyManifest {
+    type Resource = EnemyTypes;
+
+    fn build(&self) -> Result<EnemyTypes, String> {
+        for (key, enemy) in self.enemies.iter() {
+            enemy
+                .check()
+                .map_err(|problem| format!("enemy \"{}\" {}", key, problem))?;
+        }
+        let total_weight: f32 = self.enemies.values().map(|enemy| enemy.spawn_weight).sum();
+        if total_weight <= 0.0 {
+            return Err("no enemy has a spawn_weight above 0".to_string());
+        }
+
+        // a boss that can't fight is left out, rather than the whole manifest
+        let boss = self
+            .boss
+            .clone()
+            .filter(|boss| match boss.check(&self.enemies) {
+                Ok(()) => true,
+                Err(problem) => {
+                    warn!(
+                        "{}: boss \"{}\" {}, leaving it out",
+                        ENEMY_MANIFEST, boss.name, problem
+                    );
+                    false
+                }
+            });
+
+        Ok(EnemyTypes {
+            types: self.enemies.clone(),
+            boss,
+        })
+    }
+}
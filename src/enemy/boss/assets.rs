@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::collision::components::Collider;
+use crate::common::resources::SpriteRegistry;
+use crate::enemy::assets::EnemyType;
+
+/// The boss, from the `boss` entry of `assets/manifest.enemies.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossType {
+    /// Shown over the boss's health bar.
+    pub name: String,
+    /// `SpriteRegistry` key of the sheet drawn while alive.
+    pub sprite: String,
+    #[serde(default = "default_walk_animation")]
+    pub walk_animation: String,
+    /// `SpriteRegistry` key of the sheet drawn once defeated.
+    pub death_sprite: String,
+    #[serde(default = "default_death_animation")]
+    pub death_animation: String,
+    /// Animation of the `death_sprite` sheet held while hurt.
+    #[serde(default = "default_hurt_animation")]
+    pub hurt_animation: String,
+    pub health: u32,
+    /// Points for whoever lands the final blow.
+    pub score: u32,
+    /// Hitbox, in pixels of the `sprite` sheet.
+    pub collider: Collider,
+    pub trigger: BossTrigger,
+    /// `EnemyTypes` key of the enemies it summons.
+    pub minion: String,
+    /// How it fights, from full health down. Each phase takes over once the
+    /// boss's health drops to its `health` share.
+    pub phases: Vec<BossPhase>,
+}
+
+/// What brings the boss in. The waves stop while it is out.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossTrigger {
+    /// Comes in instead of this wave.
+    Wave(u32),
+    /// Comes in once the players' scores add up to this.
+    Score(u32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossPhase {
+    /// Share of its health, from 0 to 1, the boss is down to when the phase
+    /// starts.
+    pub health: f32,
+    /// How fast it sweeps up and down, in the same units as enemy speeds.
+    pub speed: f32,
+    /// Attacks, taken in turn, one every `attack_interval` seconds.
+    pub attacks: Vec<BossAttack>,
+    pub attack_interval: f32,
+    /// Minions called in every `summon_interval` seconds. None if 0.
+    #[serde(default)]
+    pub minions: u32,
+    #[serde(default)]
+    pub summon_interval: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BossAttack {
+    /// `count` bones fanned across `spread` degrees at the nearest player.
+    Fan { count: u32, spread: f32, speed: f32 },
+    /// `count` bones thrown out in every direction.
+    Ring { count: u32, speed: f32 },
+}
+
+impl BossType {
+    /// What, if anything, stops the boss from being drawn with the sheets in
+    /// `sprite_registry`.
+    pub fn check_sprites(&self, sprite_registry: &SpriteRegistry) -> Result<(), String> {
+        sprite_registry.check(&self.sprite, &[&self.walk_animation])?;
+        sprite_registry.check(
+            &self.death_sprite,
+            &[&self.hurt_animation, &self.death_animation],
+        )
+    }
+
+    /// What, if anything, stops the boss from being brought in. `enemies`
+    /// are the types it may summon from.
+    pub fn check(&self, enemies: &BTreeMap<String, EnemyType>) -> Result<(), String> {
+        if self.phases.is_empty() {
+            return Err("has no phases".to_string());
+        }
+        if !enemies.contains_key(&self.minion) {
+            return Err(format!("summons \"{}\", which isn't an enemy", self.minion));
+        }
+        for (index, phase) in self.phases.iter().enumerate() {
+            phase
+                .check()
+                .map_err(|problem| format!("phase {} {}", index, problem))?;
+        }
+        Ok(())
+    }
+}
+
+impl BossPhase {
+    /// What, if anything, stops the phase from being fought.
+    fn check(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.health) {
+            return Err(format!("starts at a health share of {}", self.health));
+        }
+        if !self.speed.is_finite() {
+            return Err(format!("has a speed of {}", self.speed));
+        }
+        // both timers repeat, so must take some time to run out
+        if !self.attack_interval.is_finite() || self.attack_interval <= 0.0 {
+            return Err(format!(
+                "has an attack_interval of {}",
+                self.attack_interval
+            ));
+        }
+        if self.minions > 0 && (!self.summon_interval.is_finite() || self.summon_interval <= 0.0) {
+            return Err(format!(
+                "summons {} minions every {} seconds",
+                self.minions, self.summon_interval
+            ));
+        }
+        if !self.summon_interval.is_finite() || self.summon_interval < 0.0 {
+            return Err(format!("has a summon_interval of {}", self.summon_interval));
+        }
+        Ok(())
+    }
+}
+
+fn default_walk_animation() -> String {
+    "walk".to_string()
+}
+
+fn default_death_animation() -> String {
+    "die".to_string()
+}
+
+fn default_hurt_animation() -> String {
+    "hurt".to_string()
+}
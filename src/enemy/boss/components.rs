@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::enemy::boss::assets::BossPhase;
+
+/// Where the boss is up to in its fight.
+#[derive(Component, Debug)]
+pub struct Boss {
+    /// Index of the phase it is in.
+    pub phase: usize,
+    /// How many attacks it has made this phase.
+    pub attacks: usize,
+    pub attack_timer: Timer,
+    pub summon_timer: Timer,
+    /// 1.0 while sweeping up, -1.0 while sweeping down.
+    pub sweep: f32,
+}
+
+impl Boss {
+    pub fn new(phase: &BossPhase) -> Self {
+        let mut boss = Self {
+            phase: 0,
+            attacks: 0,
+            attack_timer: Timer::default(),
+            summon_timer: Timer::default(),
+            sweep: 1.0,
+        };
+        boss.enter_phase(0, phase);
+        boss
+    }
+
+    pub fn enter_phase(&mut self, index: usize, phase: &BossPhase) {
+        self.phase = index;
+        self.attacks = 0;
+        self.attack_timer = Timer::from_seconds(phase.attack_interval, TimerMode::Repeating);
+        self.summon_timer = Timer::from_seconds(phase.summon_interval, TimerMode::Repeating);
+    }
+}
+
+/// The boss's name and health bar, along the bottom of the screen.
+#[derive(Component)]
+pub struct BossHud;
+
+/// The filled part of the boss's health bar.
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct VictoryBanner;
@@ -0,0 +1,72 @@
+pub mod assets;
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::common::TickSet;
+use crate::enemy::boss::resources::BossEncounter;
+use crate::enemy::boss::systems::{
+    boss_attack_system, boss_health_bar_system, boss_movement_system, boss_phase_system,
+    boss_reset_system, boss_reward_system, boss_summon_system, boss_trigger_system,
+    boss_victory_system,
+};
+use crate::enemy::EnemySystemSet;
+use crate::game::states::GameState;
+use crate::game::GameEventSystemSet;
+use crate::wave::systems::wave_director_system;
+use crate::wave::WaveSystemSet;
+
+/// `EnemyVariant` of the boss, after its entry in the enemy manifest.
+pub const BOSS_VARIANT: &str = "boss";
+/// How far in from the right edge the boss stops to fight.
+pub const BOSS_HOLD_DISTANCE: f32 = 250.0;
+
+/// Runs the boss fight, after the other enemies have moved.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct BossSystemSet;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BossEncounter>()
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    BossSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(EnemySystemSet)
+                        .before(WaveSystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
+            .add_systems(
+                (
+                    boss_phase_system,
+                    boss_movement_system,
+                    boss_attack_system,
+                    boss_summon_system,
+                    boss_health_bar_system,
+                )
+                    .chain()
+                    .in_set(BossSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                boss_trigger_system
+                    .after(wave_director_system)
+                    .in_set(WaveSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_systems(
+                (boss_reward_system, boss_victory_system)
+                    .chain()
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(boss_reset_system.in_schedule(OnExit(GameState::GameOver)))
+            .add_system(boss_reset_system.in_schedule(OnExit(GameState::Victory)));
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossEncounter {
+    /// The boss hasn't been triggered yet.
+    #[default]
+    Waiting,
+    Fighting,
+    Defeated,
+}
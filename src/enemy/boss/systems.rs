@@ -0,0 +1,460 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::animation::components::AnimationController;
+use crate::common::components::{Health, Interpolated, Movable, Velocity, Vitality};
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
+use crate::common::{BASE_SPEED, SCROLL_X_VELOCITY, SCROLL_Y_VELOCITY, TIME_STEP};
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::boss::assets::{BossAttack, BossTrigger};
+use crate::enemy::boss::components::{Boss, BossHealthBar, BossHud, VictoryBanner};
+use crate::enemy::boss::resources::BossEncounter;
+use crate::enemy::boss::{BOSS_HOLD_DISTANCE, BOSS_VARIANT};
+use crate::enemy::bundles::{EnemyBundle, EnemyDeadBundle};
+use crate::enemy::components::{Enemy, EnemyDead, EnemyVariant};
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::utils::{nearest_target, spawn_bone, spawn_enemy};
+use crate::game::events::BossDefeated;
+use crate::game::states::GameState;
+use crate::player::components::{Player, PlayerVariant, Score};
+use crate::wave::resources::{WaveDirector, WavePhase};
+use crate::wave::FORMATION_SPACING;
+
+/// Whether the waves should carry on: the boss hasn't come in yet.
+pub fn boss_encounter_waiting(boss_encounter: Res<BossEncounter>) -> bool {
+    *boss_encounter == BossEncounter::Waiting
+}
+
+/// Bring the boss in once its trigger is met, along with its health bar.
+#[allow(clippy::too_many_arguments)]
+pub fn boss_trigger_system(
+    mut commands: Commands,
+    mut boss_encounter: ResMut<BossEncounter>,
+    enemy_types: Res<EnemyTypes>,
+    wave_director: Res<WaveDirector>,
+    player_query: Query<&Score, With<Player>>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    asset_server: Res<AssetServer>,
+) {
+    if *boss_encounter != BossEncounter::Waiting {
+        return;
+    }
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+
+    let triggered = match boss_type.trigger {
+        BossTrigger::Wave(number) => {
+            wave_director.phase == WavePhase::Intermission && wave_director.number + 1 >= number
+        }
+        BossTrigger::Score(score) => {
+            player_query.iter().map(|score| score.value).sum::<u32>() >= score
+        }
+    };
+    if !triggered {
+        return;
+    }
+
+    *boss_encounter = BossEncounter::Fighting;
+    info!("{} is coming", boss_type.name);
+
+    let boss_sprite = sprite_registry.get(&boss_type.sprite);
+    let walk_clip = boss_sprite.clip(&boss_type.walk_animation);
+    let hurt_clip = sprite_registry
+        .get(&boss_type.death_sprite)
+        .clip(&boss_type.hurt_animation)
+        .once();
+
+    commands.spawn((
+        EnemyBundle {
+            entity: Enemy,
+            variant: EnemyVariant(BOSS_VARIANT.to_string()),
+            vitality: Vitality::Alive,
+            health: Health::new(boss_type.health),
+            collider: boss_type.collider,
+            animation: AnimationController::new("walk", walk_clip.clone())
+                .with_clip("hurt", hurt_clip),
+            movable: Movable {
+                auto_despawn: false,
+            },
+            velocity: Velocity::default(),
+            interpolated: Interpolated::default(),
+            sprite_sheet: SpriteSheetBundle {
+                texture_atlas: boss_sprite.atlas(),
+                sprite: TextureAtlasSprite::new(walk_clip.first),
+                transform: Transform {
+                    translation: Vec3::new(playfield.width / 2.0 + 200.0, 0.0, 10.0),
+                    rotation: boss_sprite.rotation(),
+                    scale: Vec3::splat(boss_sprite.sheet.scale),
+                },
+                ..Default::default()
+            },
+        },
+        Boss::new(&boss_type.phases[0]),
+    ));
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(20.0),
+                        left: Val::Percent(20.0),
+                        ..default()
+                    },
+                    size: Size::width(Val::Percent(60.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            BossHud,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                boss_type.name.clone(),
+                TextStyle {
+                    font,
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(16.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.1, 0.1, 0.1, 0.8).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: Color::rgb(0.8, 0.1, 0.1).into(),
+                            ..default()
+                        },
+                        BossHealthBar,
+                    ));
+                });
+        });
+}
+
+/// Move the boss on to the next phase as its health drops. Runs before the
+/// rest of the boss's systems, which look its phase up.
+pub fn boss_phase_system(
+    mut boss_query: Query<(&mut Boss, &Health)>,
+    enemy_types: Res<EnemyTypes>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+
+    for (mut boss, boss_health) in boss_query.iter_mut() {
+        // a reload may have left the boss with fewer phases than it is on
+        let last_phase = boss_type.phases.len() - 1;
+        if enemy_types.is_changed() && boss.phase > last_phase {
+            boss.enter_phase(last_phase, &boss_type.phases[last_phase]);
+        }
+
+        let health_left = boss_health.current as f32 / boss_health.max as f32;
+        let phase = boss_type
+            .phases
+            .iter()
+            .rposition(|phase| health_left <= phase.health)
+            .unwrap_or(0);
+
+        if phase > boss.phase {
+            boss.enter_phase(phase, &boss_type.phases[phase]);
+        }
+    }
+}
+
+/// Walk the boss in, then sweep up and down in front of the players.
+pub fn boss_movement_system(
+    mut boss_query: Query<(&mut Boss, &mut Transform)>,
+    enemy_types: Res<EnemyTypes>,
+    playfield: Res<Playfield>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+    let hold_x = playfield.width / 2.0 - BOSS_HOLD_DISTANCE;
+    let sweep_limit = playfield.height / 2.0 - 150.0;
+
+    for (mut boss, mut boss_transform) in boss_query.iter_mut() {
+        let step = boss_type.phases[boss.phase].speed * TIME_STEP * BASE_SPEED;
+        let boss_translation = &mut boss_transform.translation;
+
+        if boss_translation.x > hold_x {
+            boss_translation.x = (boss_translation.x - step).max(hold_x);
+            continue;
+        }
+
+        boss_translation.y += boss.sweep * step;
+        if boss_translation.y.abs() >= sweep_limit {
+            boss_translation.y = boss_translation.y.clamp(-sweep_limit, sweep_limit);
+            boss.sweep = -boss.sweep;
+        }
+    }
+}
+
+/// Throw the current phase's attacks in turn, once the boss is on screen.
+#[allow(clippy::type_complexity)]
+pub fn boss_attack_system(
+    mut commands: Commands,
+    mut boss_query: Query<(&mut Boss, &Transform)>,
+    player_query: Query<(&Transform, &Vitality), (With<Player>, Without<Boss>)>,
+    enemy_types: Res<EnemyTypes>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    fixed_time: Res<FixedTime>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+    let targets: Vec<Vec3> = player_query
+        .iter()
+        .filter(|(_, player_vitality)| **player_vitality == Vitality::Alive)
+        .map(|(player_transform, _)| player_transform.translation)
+        .collect();
+
+    for (mut boss, boss_transform) in boss_query.iter_mut() {
+        let boss_translation = boss_transform.translation;
+        if boss_translation.x >= playfield.width / 2.0 - 20.0 {
+            continue;
+        }
+
+        boss.attack_timer.tick(fixed_time.period);
+        if !boss.attack_timer.just_finished() {
+            continue;
+        }
+
+        let phase = &boss_type.phases[boss.phase];
+        if phase.attacks.is_empty() {
+            continue;
+        }
+        let attack = phase.attacks[boss.attacks % phase.attacks.len()];
+        boss.attacks += 1;
+
+        let mut throw = |angle: f32, speed: f32| {
+            let direction = Vec2::from_angle(angle);
+            spawn_bone(
+                &mut commands,
+                &sprite_registry,
+                boss_translation,
+                direction,
+                speed,
+            );
+        };
+
+        match attack {
+            BossAttack::Fan {
+                count,
+                spread,
+                speed,
+            } => {
+                let aim = nearest_target(boss_translation, &targets)
+                    .and_then(|target| (target - boss_translation).truncate().try_normalize())
+                    .unwrap_or(Vec2::NEG_X);
+                let aim_angle = aim.y.atan2(aim.x);
+                let spread = spread.to_radians();
+
+                for index in 0..count {
+                    let offset = match count {
+                        1 => 0.0,
+                        _ => spread * (index as f32 / (count - 1) as f32 - 0.5),
+                    };
+                    throw(aim_angle + offset, speed);
+                }
+            }
+            BossAttack::Ring { count, speed } => {
+                for index in 0..count {
+                    throw(TAU * index as f32 / count as f32, speed);
+                }
+            }
+        }
+    }
+}
+
+/// Call in minions beside the boss, in phases that have any.
+#[allow(clippy::too_many_arguments)]
+pub fn boss_summon_system(
+    mut commands: Commands,
+    mut boss_query: Query<(&mut Boss, &Transform)>,
+    enemy_types: Res<EnemyTypes>,
+    difficulty: Res<Difficulty>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
+    fixed_time: Res<FixedTime>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+    let rng = &mut game_rng.enemies;
+    let y_limit = playfield.height / 2.0 - 50.0;
+
+    for (mut boss, boss_transform) in boss_query.iter_mut() {
+        let phase = &boss_type.phases[boss.phase];
+        if phase.minions == 0 {
+            continue;
+        }
+
+        boss.summon_timer.tick(fixed_time.period);
+        if !boss.summon_timer.just_finished() {
+            continue;
+        }
+
+        let boss_translation = boss_transform.translation;
+        for index in 0..phase.minions {
+            let offset = (index as f32 - (phase.minions - 1) as f32 / 2.0) * FORMATION_SPACING;
+            let position = Vec2::new(
+                boss_translation.x + FORMATION_SPACING,
+                (boss_translation.y + offset).clamp(-y_limit, y_limit),
+            );
+            spawn_enemy(
+                &mut commands,
+                &sprite_registry,
+                &enemy_types,
+                &difficulty,
+                &boss_type.minion,
+                position,
+                rng,
+            );
+        }
+    }
+}
+
+pub fn boss_health_bar_system(
+    boss_query: Query<&Health, With<Boss>>,
+    mut health_bar_query: Query<&mut Style, With<BossHealthBar>>,
+) {
+    for boss_health in boss_query.iter() {
+        let health_left = boss_health.current as f32 / boss_health.max as f32;
+        for mut health_bar_style in health_bar_query.iter_mut() {
+            health_bar_style.size.width = Val::Percent(health_left * 100.0);
+        }
+    }
+}
+
+/// Award the boss's points to whoever beat it.
+pub fn boss_reward_system(
+    mut boss_defeated_events: EventReader<BossDefeated>,
+    mut player_query: Query<(&PlayerVariant, &mut Score), With<Player>>,
+    enemy_types: Res<EnemyTypes>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+
+    for boss_defeated in boss_defeated_events.iter() {
+        for (player_variant, mut player_score) in player_query.iter_mut() {
+            if *player_variant == boss_defeated.killer {
+                player_score.value += boss_type.score;
+            }
+        }
+    }
+}
+
+/// Leave the boss's body behind, take down its health bar and win the game.
+#[allow(clippy::too_many_arguments)]
+pub fn boss_victory_system(
+    mut commands: Commands,
+    mut boss_defeated_events: EventReader<BossDefeated>,
+    mut boss_encounter: ResMut<BossEncounter>,
+    hud_query: Query<Entity, With<BossHud>>,
+    enemy_types: Res<EnemyTypes>,
+    sprite_registry: Res<SpriteRegistry>,
+    asset_server: Res<AssetServer>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(boss_type) = &enemy_types.boss else {
+        return;
+    };
+
+    for boss_defeated in boss_defeated_events.iter() {
+        *boss_encounter = BossEncounter::Defeated;
+        game_state_next_state.set(GameState::Victory);
+
+        for hud_entity in hud_query.iter() {
+            commands.entity(hud_entity).despawn_recursive();
+        }
+
+        let death_sprite = sprite_registry.get(&boss_type.death_sprite);
+        let death_clip = death_sprite.clip(&boss_type.death_animation).once();
+
+        commands.spawn(EnemyDeadBundle {
+            entity: EnemyDead,
+            variant: EnemyVariant(BOSS_VARIANT.to_string()),
+            animation: AnimationController::new("die", death_clip.clone()),
+            movable: Movable { auto_despawn: true },
+            velocity: Velocity {
+                x: SCROLL_X_VELOCITY,
+                y: SCROLL_Y_VELOCITY,
+            },
+            interpolated: Interpolated::default(),
+            sprite_sheet: SpriteSheetBundle {
+                texture_atlas: death_sprite.atlas(),
+                sprite: TextureAtlasSprite::new(death_clip.first),
+                transform: Transform {
+                    translation: boss_defeated.position.truncate().extend(0.0),
+                    scale: Vec3::splat(death_sprite.sheet.scale),
+                    rotation: death_sprite.rotation(),
+                },
+                ..Default::default()
+            },
+        });
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            top: Val::Percent(30.0),
+                            ..default()
+                        },
+                        size: Size::width(Val::Percent(100.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                VictoryBanner,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    format!("{} defeated!", boss_type.name),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 80.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+    }
+}
+
+/// Get the boss ready to come in again for a new game.
+#[allow(clippy::type_complexity)]
+pub fn boss_reset_system(
+    mut commands: Commands,
+    mut boss_encounter: ResMut<BossEncounter>,
+    hud_query: Query<Entity, Or<(With<BossHud>, With<VictoryBanner>)>>,
+) {
+    *boss_encounter = BossEncounter::Waiting;
+
+    for hud_entity in hud_query.iter() {
+        commands.entity(hud_entity).despawn_recursive();
+    }
+}
@@ -1,25 +1,18 @@
 use bevy::prelude::*;
 
-use crate::common::components::{
-    AnimationIndices, AnimationTimer, EntityLocation, Movable, Velocity, Vitality,
-};
-use crate::enemy::components::{Enemy, EnemyDead, EnemyDeadLocation, EnemyVariant};
-
-#[derive(Bundle)]
-pub struct EnemyDeadLocationBundle {
-    pub entity: EnemyDeadLocation,
-    pub variant: EnemyVariant,
-    pub location: EntityLocation,
-}
+use crate::animation::components::AnimationController;
+use crate::collision::components::Collider;
+use crate::common::components::{Health, Interpolated, Movable, Velocity, Vitality};
+use crate::enemy::components::{Bone, Enemy, EnemyDead, EnemyVariant};
 
 #[derive(Bundle)]
 pub struct EnemyDeadBundle {
     pub entity: EnemyDead,
     pub variant: EnemyVariant,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
+    pub animation: AnimationController,
     pub movable: Movable,
     pub velocity: Velocity,
+    pub interpolated: Interpolated,
 
     #[bundle]
     pub sprite_sheet: SpriteSheetBundle,
@@ -30,10 +23,23 @@ pub struct EnemyBundle {
     pub entity: Enemy,
     pub variant: EnemyVariant,
     pub vitality: Vitality,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
+    pub health: Health,
+    pub collider: Collider,
+    pub animation: AnimationController,
     pub movable: Movable,
     pub velocity: Velocity,
+    pub interpolated: Interpolated,
+
+    #[bundle]
+    pub sprite_sheet: SpriteSheetBundle,
+}
+
+#[derive(Bundle)]
+pub struct BoneBundle {
+    pub entity: Bone,
+    pub collider: Collider,
+    pub velocity: Velocity,
+    pub interpolated: Interpolated,
 
     #[bundle]
     pub sprite_sheet: SpriteSheetBundle,
@@ -1,18 +1,75 @@
 use bevy::prelude::*;
 
+use crate::enemy::HURT_TIME;
+
 #[derive(Component)]
 pub struct Enemy;
 
-#[derive(Component, Default, Clone, Copy)]
-pub enum EnemyVariant {
-    #[default]
-    Zombie,
-    Skelton,
-    Goblin,
+/// Which enemy type, from the enemy manifest, an entity is.
+#[derive(Component, Default, Clone, Debug, PartialEq, Eq)]
+pub struct EnemyVariant(pub String);
+
+/// A tougher, faster, bigger enemy worth more points. The difficulty
+/// decides how often one turns up.
+#[derive(Component)]
+pub struct Elite;
+
+/// Walks left, drifting toward the nearest player's height.
+#[derive(Component)]
+pub struct Shambler {
+    pub homing: f32,
+}
+
+/// Holds back from the nearest player and throws bones at them until it runs
+/// out, then walks on.
+#[derive(Component)]
+pub struct Skirmisher {
+    pub distance: f32,
+    pub throw_timer: Timer,
+    pub bone_speed: f32,
+    pub bones: u32,
 }
 
+/// Winds up once a player is in range, then charges in a straight line.
 #[derive(Component)]
-pub struct EnemyDeadLocation;
+pub struct Charger {
+    pub range: f32,
+    pub speed: f32,
+    pub wind_up: Timer,
+    pub state: ChargeState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChargeState {
+    Approach,
+    WindUp,
+    /// Charging along a unit direction.
+    Charging(Vec2),
+}
+
+/// A bone thrown by a skeleton.
+#[derive(Component)]
+pub struct Bone;
+
+/// An enemy that has just taken damage and survived. It is tinted and
+/// pushed back the way the shot was going until the timer runs out.
+#[derive(Component)]
+pub struct Hurt {
+    pub timer: Timer,
+    pub knockback: f32,
+    /// Unit direction the enemy is pushed in.
+    pub direction: Vec2,
+}
+
+impl Hurt {
+    pub fn new(knockback: f32, direction: Vec2) -> Self {
+        Self {
+            timer: Timer::from_seconds(HURT_TIME, TimerMode::Once),
+            knockback,
+            direction,
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct EnemyDead;
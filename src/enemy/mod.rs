@@ -1,75 +1,45 @@
 use bevy::prelude::*;
 
+pub mod assets;
+pub mod boss;
 pub mod bundles;
 pub mod components;
 pub mod resources;
 pub mod systems;
+pub mod utils;
 
-use crate::common::SpriteSheet;
-use crate::enemy::resources::EnemySpawnTimer;
+use crate::common::ManifestPlugin;
+use crate::common::TickSet;
+use crate::enemy::assets::EnemyManifest;
+use crate::enemy::boss::BossPlugin;
 use crate::enemy::systems::{
-    enemies_spawn_over_time_system, enemy_dead_movement_system, enemy_dead_spawn_system,
-    enemy_hit_player_system, enemy_movement_system, enemy_spawn_system,
-    enemy_spawn_timer_tick_system, respawn_enemy_system,
+    bone_hit_player_system, bone_movement_system, charger_system, enemy_dead_movement_system,
+    enemy_dead_spawn_system, enemy_despawn_system, enemy_hit_player_system, enemy_hurt_system,
+    enemy_sprites_check_system, respawn_enemy_system, shambler_system, skirmisher_system,
 };
+use crate::game::resources::GameDataLoaded;
 use crate::game::states::GameState;
+use crate::game::GameEventSystemSet;
+use crate::player::PlayerSystemSet;
 
-pub const ENEMY1_SPRITE: SpriteSheet = SpriteSheet {
-    file: "zombie-walk.png",
-    width: 32.0,
-    height: 32.0,
-    scale: 3.0,
-    columns: 11,
-    rows: 4,
-};
-
-pub const ENEMY1_DEAD_SPRITE: SpriteSheet = SpriteSheet {
-    file: "zombie-dead.png",
-    width: 32.0,
-    height: 32.0,
-    scale: 3.0,
-    columns: 8,
-    rows: 4,
-};
-
-pub const ENEMY2_SPRITE: SpriteSheet = SpriteSheet {
-    file: "skeleton-walk.png",
-    width: 22.0,
-    height: 33.0,
-    scale: 3.0,
-    columns: 13,
-    rows: 1,
-};
-
-pub const ENEMY2_DEAD_SPRITE: SpriteSheet = SpriteSheet {
-    file: "skeleton-dead.png",
-    width: 33.0,
-    height: 32.0,
-    scale: 3.0,
-    columns: 15,
-    rows: 1,
-};
-
-pub const ENEMY3_SPRITE: SpriteSheet = SpriteSheet {
-    file: "goblin-walk.png",
-    width: 150.0,
-    height: 150.0,
-    scale: 2.5,
-    columns: 8,
-    rows: 1,
-};
-
-pub const ENEMY3_DEAD_SPRITE: SpriteSheet = SpriteSheet {
-    file: "goblin-dead.png",
-    width: 150.0,
-    height: 150.0,
-    scale: 2.5,
-    columns: 4,
-    rows: 1,
-};
-
-pub const ENEMY_SPAWN_TIME: f32 = 2.0;
+/// How many enemies the F1 debug key spawns.
 pub const NUMBER_OF_ENEMIES: u8 = 10;
+pub const ENEMY_MANIFEST: &str = "manifest.enemies.ron";
+/// Running into an enemy while diamond powered scores this many times what
+/// shooting it would.
+pub const DIAMOND_SCORE_MULTIPLIER: u32 = 10;
+/// Seconds an enemy flashes and is pushed back for after being hit.
+pub const HURT_TIME: f32 = 0.15;
+pub const HURT_COLOR: Color = Color::rgb(1.0, 0.3, 0.3);
+/// Elites have this many times the hit points and speed of the enemy they
+/// are based on, are drawn bigger and tinted, and score more.
+pub const ELITE_HEALTH_MULTIPLIER: f32 = 2.0;
+pub const ELITE_SPEED_MULTIPLIER: f32 = 1.3;
+pub const ELITE_SCALE: f32 = 1.25;
+pub const ELITE_COLOR: Color = Color::rgb(1.0, 0.8, 0.3);
+pub const ELITE_SCORE_MULTIPLIER: u32 = 3;
+/// Bones are drawn with the fireball sprite, tinted.
+pub const BONE_COLOR: Color = Color::rgb(0.9, 0.9, 0.8);
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct EnemySystemSet;
@@ -77,20 +47,43 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EnemySpawnTimer>()
-            .configure_set(EnemySystemSet.run_if(in_state(GameState::Playing)))
-            .add_startup_system(enemy_spawn_system.in_base_set(StartupSet::PostStartup))
+        app.add_plugin(BossPlugin)
+            .add_plugin(ManifestPlugin::<EnemyManifest>::default())
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    EnemySystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(PlayerSystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
             .add_systems(
                 (
-                    enemy_movement_system,
-                    enemy_spawn_timer_tick_system,
-                    enemies_spawn_over_time_system,
+                    shambler_system,
+                    skirmisher_system,
+                    charger_system,
+                    enemy_hurt_system,
+                    enemy_despawn_system,
+                    bone_movement_system,
                     enemy_hit_player_system,
-                    enemy_dead_spawn_system,
+                    bone_hit_player_system,
                     enemy_dead_movement_system,
                 )
-                    .in_set(EnemySystemSet),
+                    .chain()
+                    .in_set(EnemySystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                enemy_dead_spawn_system
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                enemy_sprites_check_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .run_if(resource_exists::<GameDataLoaded>()),
             )
-            .add_system(respawn_enemy_system);
+            .add_system(respawn_enemy_system.run_if(resource_exists::<GameDataLoaded>()));
     }
 }
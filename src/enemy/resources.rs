@@ -1,17 +1,45 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::Resource;
-use bevy::time::{Timer, TimerMode};
+use rand::Rng;
 
-use crate::enemy::ENEMY_SPAWN_TIME;
+use crate::enemy::assets::EnemyType;
+use crate::enemy::boss::assets::BossType;
+use crate::enemy::ENEMY_MANIFEST;
 
-#[derive(Resource)]
-pub struct EnemySpawnTimer {
-    pub timer: Timer,
+/// The loaded enemy manifest. Exists once `assets/manifest.enemies.ron`
+/// has loaded.
+#[derive(Resource, Default)]
+pub struct EnemyTypes {
+    pub types: BTreeMap<String, EnemyType>,
+    pub boss: Option<BossType>,
 }
 
-impl Default for EnemySpawnTimer {
-    fn default() -> Self {
-        Self {
-            timer: Timer::from_seconds(ENEMY_SPAWN_TIME, TimerMode::Repeating),
+impl EnemyTypes {
+    /// The enemy type named `key`. Panics if the manifest has no such type.
+    pub fn get(&self, key: &str) -> &EnemyType {
+        self.types
+            .get(key)
+            .unwrap_or_else(|| panic!("no enemy type named \"{}\" in {}", key, ENEMY_MANIFEST))
+    }
+
+    /// Pick an enemy type at random, weighted by `spawn_weight`. None if
+    /// every type that could be picked has been left out for want of
+    /// sprites.
+    pub fn choose(&self, rng: &mut impl Rng) -> Option<&str> {
+        let total_weight: f32 = self.types.values().map(|enemy| enemy.spawn_weight).sum();
+        if total_weight <= 0.0 {
+            return None;
         }
+        let mut pick = rng.gen_range(0.0..total_weight);
+
+        for (key, enemy) in self.types.iter() {
+            if pick < enemy.spawn_weight {
+                return Some(key);
+            }
+            pick -= enemy.spawn_weight;
+        }
+
+        self.types.keys().next_back().map(String::as_str)
     }
 }
@@ -1,430 +1,590 @@
-use std::collections::HashSet;
-
 use rand::Rng;
 
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-
-use bevy_kira_audio::prelude::Audio;
-use bevy_kira_audio::AudioControl;
 
-use crate::enemy::bundles::{EnemyBundle, EnemyDeadBundle, EnemyDeadLocationBundle};
-use crate::enemy::components::{Enemy, EnemyDead, EnemyDeadLocation, EnemyVariant};
-use crate::enemy::resources::EnemySpawnTimer;
-use crate::enemy::{
-    ENEMY1_DEAD_SPRITE, ENEMY1_SPRITE, ENEMY2_DEAD_SPRITE, ENEMY2_SPRITE, ENEMY3_DEAD_SPRITE,
-    ENEMY3_SPRITE, NUMBER_OF_ENEMIES,
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::boss::components::Boss;
+use crate::enemy::bundles::EnemyDeadBundle;
+use crate::enemy::components::{
+    Bone, ChargeState, Charger, Elite, Enemy, EnemyDead, EnemyVariant, Hurt, Shambler, Skirmisher,
 };
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::utils::{approach, nearest_target, spawn_bone, spawn_enemy};
+use crate::enemy::{ELITE_COLOR, ENEMY_MANIFEST, HURT_COLOR, NUMBER_OF_ENEMIES};
 
-use crate::player::bundles::PlayerDeadLocationBundle;
-use crate::player::components::{Lives, Player, PlayerDeadLocation, PlayerVariant, Score};
-use crate::player::PLAYER1_SPRITE;
+use crate::player::components::{Lives, Player, PlayerVariant, Score};
 
-use crate::common::components::{
-    AnimationIndices, AnimationTimer, EntityLocation, Movable, Velocity, Vitality,
-};
-use crate::common::resources::{GameAudio, GameTextures};
-use crate::common::utils::{animate_sprite, animate_sprite_single};
+use crate::animation::components::AnimationController;
+use crate::collision::events::{BoneHitPlayer, EnemyHitPlayer};
+use crate::common::components::{Interpolated, Movable, Velocity, Vitality};
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
 use crate::common::{BASE_SPEED, SCROLL_X_VELOCITY, SCROLL_Y_VELOCITY, TIME_STEP};
 
-use crate::item::components::ItemPower;
+use crate::game::events::{EnemyKilled, KillCause, PlayerDied, PlayerHurt};
+use crate::item::assets::Effect;
+use crate::item::components::ActiveEffects;
+use crate::item::utils::shield_takes_hit;
+
+/// Leave out enemies, and the boss, that can't be drawn with the loaded
+/// sprite sheets, once either manifest is loaded. Runs before any tick, so
+/// nothing tries to spawn them.
+pub fn enemy_sprites_check_system(
+    mut enemy_types: ResMut<EnemyTypes>,
+    sprite_registry: Res<SpriteRegistry>,
+) {
+    if !(enemy_types.is_changed() || sprite_registry.is_changed()) {
+        return;
+    }
+
+    let undrawable: Vec<(String, String)> = enemy_types
+        .types
+        .iter()
+        .filter_map(|(key, enemy_type)| {
+            let problem = enemy_type.check_sprites(&sprite_registry).err()?;
+            Some((key.clone(), problem))
+        })
+        .collect();
+    for (key, problem) in undrawable.iter() {
+        warn!(
+            "{}: enemy \"{}\" {}, leaving it out",
+            ENEMY_MANIFEST, key, problem
+        );
+        enemy_types.types.remove(key);
+    }
+
+    // only touch the boss if it has to go, so this doesn't keep firing
+    let boss_problem = enemy_types.boss.as_ref().and_then(|boss| {
+        boss.check_sprites(&sprite_registry)
+            .and_then(|_| boss.check(&enemy_types.types))
+            .err()
+            .map(|problem| (boss.name.clone(), problem))
+    });
+    if let Some((name, problem)) = boss_problem {
+        warn!(
+            "{}: boss \"{}\" {}, leaving it out",
+            ENEMY_MANIFEST, name, problem
+        );
+        enemy_types.boss = None;
+    }
+}
 
+/// Spawn `NUMBER_OF_ENEMIES` random enemies, scattered just off the right
+/// edge of the playfield.
 pub fn enemy_spawn_system(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    game_textures: Res<GameTextures>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    enemy_types: Res<EnemyTypes>,
+    difficulty: Res<Difficulty>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
+    let rng = &mut game_rng.enemies;
 
-    let (spawn_area_width_start, spawn_area_width_end) =
-        (window.width() / 2.0, window.width() - window.width() / 8.0);
-    let (spawn_area_height_start, spawn_area_height_end) =
-        (-window.height() / 2.0 + 50.0, window.height() / 2.0 - 50.0);
+    let (spawn_area_width_start, spawn_area_width_end) = (
+        playfield.width / 2.0,
+        playfield.width - playfield.width / 8.0,
+    );
+    let (spawn_area_height_start, spawn_area_height_end) = (
+        -playfield.height / 2.0 + 50.0,
+        playfield.height / 2.0 - 50.0,
+    );
 
     for _ in 0..NUMBER_OF_ENEMIES {
-        let mut rng = rand::thread_rng();
-
-        let flip = rng.gen_range(0.0..10.0);
-
-        #[allow(illegal_floating_point_literal_pattern)]
-        let enemy_variant = match flip {
-            0.0..=3.0 => EnemyVariant::Zombie,
-            3.1..=7.0 => EnemyVariant::Skelton,
-            7.1..=10.0 => EnemyVariant::Goblin,
-            _ => EnemyVariant::Zombie,
-        };
-
-        let (enemy_sprite, enemy_texture, animation_indices, flip_x) = match enemy_variant {
-            EnemyVariant::Zombie => (
-                ENEMY1_SPRITE,
-                game_textures.enemy_zombie.clone(),
-                AnimationIndices {
-                    first: 33,
-                    last: 42,
-                },
-                false,
-            ),
-            EnemyVariant::Skelton => (
-                ENEMY2_SPRITE,
-                game_textures.enemy_skeleton.clone(),
-                AnimationIndices { first: 0, last: 12 },
-                true,
-            ),
-            EnemyVariant::Goblin => (
-                ENEMY3_SPRITE,
-                game_textures.enemy_goblin.clone(),
-                AnimationIndices { first: 0, last: 7 },
-                true,
-            ),
+        let Some(enemy_key) = enemy_types.choose(rng).map(str::to_string) else {
+            return;
         };
 
-        // let animation_timer = Timer::from_seconds(10.0, TimerMode::Repeating);
-        let animation_timer = AnimationTimer::default();
-
         let random_width = rng.gen_range(spawn_area_width_start..spawn_area_width_end);
         let random_height = rng.gen_range(spawn_area_height_start..spawn_area_height_end);
 
-        commands.spawn(EnemyBundle {
-            entity: Enemy,
-            variant: enemy_variant,
-            vitality: Vitality::Alive,
-            animation_indices: animation_indices,
-            animation_timer: AnimationTimer(animation_timer.clone()),
-            movable: Movable { auto_despawn: true },
-            velocity: Velocity {
-                x: rng.gen_range(0.01..0.1),
-                y: rng.gen_range(0.01..0.1),
-            },
-            sprite_sheet: SpriteSheetBundle {
-                texture_atlas: enemy_texture,
-                sprite: TextureAtlasSprite::new(animation_indices.first),
-                // transform: Transform::from_scale(Vec3::splat(3.0)),
-                transform: Transform {
-                    translation: Vec3::new(random_width, random_height, 10.0),
-                    rotation: if flip_x {
-                        Quat::from_rotation_y(std::f32::consts::PI)
-                    } else {
-                        Quat::IDENTITY
-                    },
-                    scale: Vec3::splat(enemy_sprite.scale),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-        });
+        spawn_enemy(
+            &mut commands,
+            &sprite_registry,
+            &enemy_types,
+            &difficulty,
+            &enemy_key,
+            Vec2::new(random_width, random_height),
+            rng,
+        );
     }
 }
 
 pub fn respawn_enemy_system(
     commands: Commands,
     keyboard_input: Res<Input<KeyCode>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    game_textures: Res<GameTextures>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    enemy_types: Res<EnemyTypes>,
+    difficulty: Res<Difficulty>,
+    game_rng: ResMut<GameRng>,
 ) {
     if keyboard_input.just_pressed(KeyCode::F1) {
-        enemy_spawn_system(commands, window_query, game_textures)
+        enemy_spawn_system(
+            commands,
+            playfield,
+            sprite_registry,
+            enemy_types,
+            difficulty,
+            game_rng,
+        )
     }
 }
 
-pub fn enemy_spawn_timer_tick_system(
-    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
-    time: Res<Time>,
+/// The translations of every player still alive, for enemies to go after.
+#[allow(clippy::type_complexity)]
+fn player_targets(
+    player_query: &Query<(&Transform, &Vitality), (With<Player>, Without<Enemy>)>,
+) -> Vec<Vec3> {
+    player_query
+        .iter()
+        .filter(|(_, player_vitality)| **player_vitality == Vitality::Alive)
+        .map(|(player_transform, _)| player_transform.translation)
+        .collect()
+}
+
+#[allow(clippy::type_complexity)]
+pub fn shambler_system(
+    mut enemy_query: Query<(&Shambler, &Velocity, &mut Transform), With<Enemy>>,
+    player_query: Query<(&Transform, &Vitality), (With<Player>, Without<Enemy>)>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    enemy_spawn_timer.timer.tick(time.delta());
+    let rng = &mut game_rng.enemy_ai;
+    let targets = player_targets(&player_query);
+
+    for (shambler, velocity, mut enemy_transform) in enemy_query.iter_mut() {
+        let enemy_translation = &mut enemy_transform.translation;
+        enemy_translation.x -= velocity.x * TIME_STEP * BASE_SPEED * 2.0 + 1.0;
+
+        let Some(target) = nearest_target(*enemy_translation, &targets) else {
+            continue;
+        };
+
+        // only drifts on some ticks, and only until it has passed the player
+        if rng.gen_bool(0.5) && enemy_translation.x > target.x {
+            let step = velocity.y * TIME_STEP * BASE_SPEED * shambler.homing;
+            enemy_translation.y = approach(enemy_translation.y, target.y, step);
+        }
+    }
 }
 
-pub fn enemies_spawn_over_time_system(
-    commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    enemy_spawn_timer: Res<EnemySpawnTimer>,
-    game_textures: Res<GameTextures>,
+#[allow(clippy::type_complexity)]
+pub fn skirmisher_system(
+    mut commands: Commands,
+    mut enemy_query: Query<(&mut Skirmisher, &Velocity, &mut Transform), With<Enemy>>,
+    player_query: Query<(&Transform, &Vitality), (With<Player>, Without<Enemy>)>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    fixed_time: Res<FixedTime>,
 ) {
-    if enemy_spawn_timer.timer.finished() {
-        enemy_spawn_system(commands, window_query, game_textures)
+    let targets = player_targets(&player_query);
+    let world_right_edge = playfield.width / 2.0 - 20.0;
+
+    for (mut skirmisher, velocity, mut enemy_transform) in enemy_query.iter_mut() {
+        let enemy_translation = &mut enemy_transform.translation;
+        let walk_step = velocity.x * TIME_STEP * BASE_SPEED * 2.0 + 1.0;
+
+        let target = match skirmisher.bones {
+            0 => None,
+            _ => nearest_target(*enemy_translation, &targets),
+        };
+        let Some(target) = target else {
+            enemy_translation.x -= walk_step;
+            continue;
+        };
+
+        // close in to `distance`, and back off if the player comes too near
+        let gap = enemy_translation.x - target.x;
+        if gap > skirmisher.distance {
+            enemy_translation.x -= walk_step;
+        } else if gap < skirmisher.distance * 0.75 && enemy_translation.x < world_right_edge {
+            enemy_translation.x += walk_step / 2.0;
+        }
+
+        let line_up_step = velocity.y * TIME_STEP * BASE_SPEED / 2.0;
+        enemy_translation.y = approach(enemy_translation.y, target.y, line_up_step);
+
+        // only throw once on screen
+        if enemy_translation.x >= world_right_edge {
+            continue;
+        }
+
+        skirmisher.throw_timer.tick(fixed_time.period);
+        if !skirmisher.throw_timer.just_finished() {
+            continue;
+        }
+
+        skirmisher.bones -= 1;
+
+        let direction = (target - *enemy_translation)
+            .truncate()
+            .try_normalize()
+            .unwrap_or(Vec2::NEG_X);
+
+        spawn_bone(
+            &mut commands,
+            &sprite_registry,
+            *enemy_translation,
+            direction,
+            skirmisher.bone_speed,
+        );
     }
 }
 
-pub fn enemy_movement_system(
+#[allow(clippy::type_complexity)]
+pub fn charger_system(
+    mut enemy_query: Query<(&mut Charger, &Velocity, &mut Transform), With<Enemy>>,
+    player_query: Query<(&Transform, &Vitality), (With<Player>, Without<Enemy>)>,
+    playfield: Res<Playfield>,
+    fixed_time: Res<FixedTime>,
+) {
+    let targets = player_targets(&player_query);
+    let world_right_edge = playfield.width / 2.0 - 20.0;
+
+    for (mut charger, velocity, mut enemy_transform) in enemy_query.iter_mut() {
+        let enemy_translation = &mut enemy_transform.translation;
+        let walk_step = velocity.x * TIME_STEP * BASE_SPEED * 2.0 + 1.0;
+        let target = nearest_target(*enemy_translation, &targets);
+
+        match charger.state {
+            ChargeState::Approach => {
+                enemy_translation.x -= walk_step;
+
+                let Some(target) = target else {
+                    continue;
+                };
+                let gap = enemy_translation.x - target.x;
+                if enemy_translation.x < world_right_edge && gap > 0.0 && gap < charger.range {
+                    charger.wind_up.reset();
+                    charger.state = ChargeState::WindUp;
+                }
+            }
+            ChargeState::WindUp => {
+                // rear back before the charge
+                enemy_translation.x += walk_step / 4.0;
+
+                charger.wind_up.tick(fixed_time.period);
+                if charger.wind_up.finished() {
+                    let direction = target
+                        .and_then(|target| (target - *enemy_translation).truncate().try_normalize())
+                        .filter(|direction| direction.x < 0.0)
+                        .unwrap_or(Vec2::NEG_X);
+                    charger.state = ChargeState::Charging(direction);
+                }
+            }
+            ChargeState::Charging(direction) => {
+                let charge_step = direction * charger.speed * TIME_STEP * BASE_SPEED;
+                *enemy_translation += charge_step.extend(0.0);
+            }
+        }
+    }
+}
+
+/// Despawn enemies that have left the playfield, past the left edge or far
+/// off the top or bottom, where charges and bone throwers can end up. Each
+/// one that got past the left edge costs the first player still alive a
+/// point.
+#[allow(clippy::type_complexity)]
+pub fn enemy_despawn_system(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Transform, &Movable), (With<Enemy>, Without<EnemyDead>)>,
+    mut player_query: Query<(&Vitality, &mut Score), With<Player>>,
+    playfield: Res<Playfield>,
+) {
+    let window_margin = -playfield.width / 2.0 - 20.0;
+    let vertical_margin = playfield.height / 2.0 + 100.0;
+
+    for (enemy_entity, enemy_transform, movable) in enemy_query.iter() {
+        if !movable.auto_despawn {
+            continue;
+        }
+
+        let enemy_translation = enemy_transform.translation;
+        let got_past = enemy_translation.x < window_margin;
+        if !got_past && enemy_translation.y.abs() < vertical_margin {
+            continue;
+        }
+
+        commands.entity(enemy_entity).despawn();
+
+        if !got_past {
+            continue;
+        }
+        let first_alive = player_query
+            .iter_mut()
+            .find(|(player_vitality, _)| **player_vitality == Vitality::Alive);
+        if let Some((_, mut player_score)) = first_alive {
+            if player_score.value > 0 {
+                player_score.value -= 1;
+            }
+        }
+    }
+}
+
+pub fn bone_movement_system(
+    mut commands: Commands,
+    mut bone_query: Query<(Entity, &Velocity, &mut Transform), With<Bone>>,
+    playfield: Res<Playfield>,
+) {
+    for (bone_entity, velocity, mut bone_transform) in bone_query.iter_mut() {
+        let bone_translation = &mut bone_transform.translation;
+        bone_translation.x += velocity.x * TIME_STEP * BASE_SPEED;
+        bone_translation.y += velocity.y * TIME_STEP * BASE_SPEED;
+
+        // despawn when out of screen
+        if bone_translation.x.abs() > playfield.width / 2.0 + 20.0
+            || bone_translation.y.abs() > playfield.height / 2.0 + 20.0
+        {
+            commands.entity(bone_entity).despawn();
+        }
+    }
+}
+
+/// Tint hurt enemies, show them reeling and push them back, easing off until
+/// the hurt wears off.
+#[allow(clippy::type_complexity)]
+pub fn enemy_hurt_system(
     mut commands: Commands,
     mut enemy_query: Query<
         (
             Entity,
-            &Velocity,
+            &mut Hurt,
             &mut Transform,
-            &Movable,
-            &AnimationIndices,
-            &mut AnimationTimer,
             &mut TextureAtlasSprite,
+            &mut AnimationController,
+            Option<&Elite>,
         ),
-        (With<Enemy>, Without<EnemyDead>),
+        With<Enemy>,
     >,
-    mut player_query: Query<(&Transform, &Vitality, &mut Score), (With<Player>, Without<Enemy>)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    time: Res<Time>,
+    fixed_time: Res<FixedTime>,
 ) {
-    let window = window_query.get_single().unwrap();
-    let mut despawned_entities: HashSet<Entity> = HashSet::new();
-
-    for (
-        enemy_entity,
-        velocity,
-        mut enemy_transform,
-        movable,
-        enemy_animation_indices,
-        mut enemy_animation_timer,
-        mut enemy_sprite,
-    ) in enemy_query.iter_mut()
+    for (enemy_entity, mut hurt, mut enemy_transform, mut enemy_sprite, mut animation, elite) in
+        enemy_query.iter_mut()
     {
-        let enemy_translation = &mut enemy_transform.translation;
-        enemy_translation.x -= velocity.x * TIME_STEP * BASE_SPEED * 2.0 + 1.0;
-
-        animate_sprite(
-            &mut enemy_sprite,
-            &enemy_animation_indices,
-            &mut enemy_animation_timer,
-            &time,
-        );
+        hurt.timer.tick(fixed_time.period);
+
+        if hurt.timer.finished() {
+            enemy_sprite.color = match elite {
+                Some(_) => ELITE_COLOR,
+                None => Color::WHITE,
+            };
+            animation.play("walk");
+            commands.entity(enemy_entity).remove::<Hurt>();
+            continue;
+        }
 
-        for (player_transform, player_vitality, mut player_score) in player_query.iter_mut() {
-            if player_vitality == &Vitality::Dead {
-                continue;
-            }
+        enemy_sprite.color = HURT_COLOR;
+        if animation.has_clip("hurt") {
+            animation.play("hurt");
+        }
+        let push = hurt.direction * hurt.knockback * hurt.timer.percent_left() * TIME_STEP;
+        enemy_transform.translation += push.extend(0.0);
+    }
+}
 
-            let player_translation = player_transform.translation;
+#[allow(clippy::type_complexity)]
+pub fn enemy_hit_player_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<EnemyHitPlayer>,
+    mut player_query: Query<
+        (
+            &PlayerVariant,
+            &mut Vitality,
+            &mut Lives,
+            &Transform,
+            &mut ActiveEffects,
+        ),
+        (With<Player>, Without<Enemy>),
+    >,
+    mut enemy_query: Query<
+        (
+            &EnemyVariant,
+            &mut Vitality,
+            &Transform,
+            Option<&Boss>,
+            Option<&Elite>,
+        ),
+        (With<Enemy>, Without<Player>),
+    >,
+    mut enemy_killed_events: EventWriter<EnemyKilled>,
+    mut player_hurt_events: EventWriter<PlayerHurt>,
+    mut player_died_events: EventWriter<PlayerDied>,
+) {
+    for collision in collision_events.iter() {
+        let Ok((
+            player,
+            mut player_vitality,
+            mut player_lives,
+            player_transform,
+            mut active_effects,
+        )) = player_query.get_mut(collision.player)
+        else {
+            continue;
+        };
+        let Ok((enemy_variant, mut enemy_vitality, enemy_transform, boss, elite)) =
+            enemy_query.get_mut(collision.enemy)
+        else {
+            continue;
+        };
 
-            if despawned_entities.contains(&enemy_entity) {
-                continue;
-            }
+        if *player_vitality == Vitality::Dead || *enemy_vitality == Vitality::Dead {
+            continue;
+        }
 
-            let mut rng = rand::thread_rng();
-            let flip = rng.gen_range(0.0..10.0);
+        // invincible players walk through enemies unharmed
+        let diamond = active_effects.is_active(Effect::Diamond);
+        if active_effects.is_active(Effect::Invincible) && !diamond {
+            continue;
+        }
 
-            if flip > 5.0 && enemy_translation.x > player_translation.x {
-                match player_translation.y > enemy_translation.y {
-                    true => enemy_translation.y += velocity.y * TIME_STEP * BASE_SPEED / 2.0,
-                    false => enemy_translation.y -= velocity.y * TIME_STEP * BASE_SPEED / 2.0,
-                }
+        if diamond {
+            // the boss has to be worn down with fireballs
+            if boss.is_some() {
+                continue;
             }
 
-            if movable.auto_despawn {
-                // despawn when out of screen
-                let window_margin = -window.width() / 2.0 - 20.0;
-                if enemy_translation.x < window_margin {
-                    commands.entity(enemy_entity).despawn();
-                    despawned_entities.insert(enemy_entity);
-
-                    if player_score.value > 0 {
-                        player_score.value -= 1;
-                    }
-                }
-            }
+            *enemy_vitality = Vitality::Dead;
+            commands.entity(collision.enemy).despawn();
+
+            enemy_killed_events.send(EnemyKilled {
+                variant: enemy_variant.clone(),
+                killer: player.clone(),
+                cause: KillCause::Diamond,
+                elite: elite.is_some(),
+                position: enemy_transform.translation,
+            });
+        } else if shield_takes_hit(&mut active_effects) {
+            player_hurt_events.send(PlayerHurt {
+                player: player.clone(),
+            });
+        } else {
+            *player_vitality = Vitality::Dead;
+            player_lives.count -= 1;
+
+            player_died_events.send(PlayerDied {
+                player: player.clone(),
+                position: player_transform.translation,
+            });
         }
     }
 }
 
-pub fn enemy_hit_player_system(
+pub fn bone_hit_player_system(
     mut commands: Commands,
+    mut collision_events: EventReader<BoneHitPlayer>,
     mut player_query: Query<
         (
             &PlayerVariant,
             &mut Vitality,
             &mut Lives,
             &Transform,
-            &mut Handle<TextureAtlas>,
-            &ItemPower,
-            &mut Score,
+            &mut ActiveEffects,
         ),
-        (With<Player>, Without<Enemy>),
+        With<Player>,
     >,
-    enemy_query: Query<(Entity, &EnemyVariant, &Transform), (With<Enemy>, Without<Player>)>,
-    game_textures: Res<GameTextures>,
-    game_audio: Res<GameAudio>,
-    audio: Res<Audio>,
+    mut player_hurt_events: EventWriter<PlayerHurt>,
+    mut player_died_events: EventWriter<PlayerDied>,
 ) {
-    for (enemy_entity, enemy_variant, enemy_transform) in enemy_query.iter() {
-        for (
+    // A bone only hits the first player it touches.
+    let mut spent_bone = None;
+
+    for collision in collision_events.iter() {
+        if spent_bone == Some(collision.bone) {
+            continue;
+        }
+        let Ok((
             player,
             mut player_vitality,
             mut player_lives,
             player_transform,
-            mut sprite_handle,
-            item_power,
-            mut player_score,
-        ) in player_query.iter_mut()
+            mut active_effects,
+        )) = player_query.get_mut(collision.player)
+        else {
+            continue;
+        };
+
+        if *player_vitality == Vitality::Dead {
+            continue;
+        }
+
+        commands.entity(collision.bone).despawn();
+        spent_bone = Some(collision.bone);
+
+        // diamond power and invincibility shrug bones off, a shield takes one
+        if active_effects.is_active(Effect::Diamond) || active_effects.is_active(Effect::Invincible)
         {
-            if *player_vitality == Vitality::Alive {
-                let distance = player_transform
-                    .translation
-                    .distance(enemy_transform.translation);
-                let player_radius = PLAYER1_SPRITE.width / 2.0;
-                let enemy_radius = ENEMY1_SPRITE.width / 2.0;
-                if distance < player_radius + enemy_radius {
-                    if item_power.diamond {
-                        audio.play(game_audio.enemy_dead.clone());
-
-                        commands.entity(enemy_entity).despawn();
-
-                        // update score
-                        player_score.value += 10;
-
-                        commands.spawn(EnemyDeadLocationBundle {
-                            entity: EnemyDeadLocation,
-                            variant: enemy_variant.clone(),
-                            location: EntityLocation {
-                                x: enemy_transform.translation.x,
-                                y: enemy_transform.translation.y,
-                                z: 0.0,
-                            },
-                        });
-                    } else {
-                        let player_ghost_sprite_atlas = match player {
-                            PlayerVariant::One => game_textures.player_one_ghost.clone(),
-                            PlayerVariant::Two => game_textures.player_two_ghost.clone(),
-                        };
-
-                        audio.play(game_audio.player_dead.clone());
-                        *player_vitality = Vitality::Dead;
-                        player_lives.count -= 1;
-
-                        *sprite_handle = player_ghost_sprite_atlas;
-
-                        // Spawn dead body
-                        commands.spawn(PlayerDeadLocationBundle {
-                            entity: PlayerDeadLocation,
-                            location: EntityLocation {
-                                x: player_transform.translation.x,
-                                y: player_transform.translation.y,
-                                z: 0.0,
-                            },
-                        });
-                        break;
-                    }
-                }
-            }
+            continue;
         }
+        if shield_takes_hit(&mut active_effects) {
+            player_hurt_events.send(PlayerHurt {
+                player: player.clone(),
+            });
+            continue;
+        }
+
+        *player_vitality = Vitality::Dead;
+        player_lives.count -= 1;
+
+        player_died_events.send(PlayerDied {
+            player: player.clone(),
+            position: player_transform.translation,
+        });
     }
 }
 
 pub fn enemy_dead_spawn_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    enemy_query: Query<(Entity, &EnemyVariant, &EntityLocation), With<EnemyDeadLocation>>,
+    sprite_registry: Res<SpriteRegistry>,
+    enemy_types: Res<EnemyTypes>,
+    mut enemy_killed_events: EventReader<EnemyKilled>,
 ) {
-    for (enemy_dead_entity, enemy_type, enemy_dead_location) in enemy_query.iter() {
-        // spawn the dead enemy sprite
-
-        let (
-            enemy_sprite,
-            enemy_texture,
-            dead_enemy_variant,
-            animation_indices,
-            animation_timer,
-            flip_x,
-        ) = match enemy_type {
-            EnemyVariant::Zombie => (
-                ENEMY1_DEAD_SPRITE,
-                game_textures.enemy_zombie_dead.clone(),
-                EnemyVariant::Zombie,
-                AnimationIndices {
-                    first: 24,
-                    last: 30,
-                },
-                AnimationTimer(Timer::from_seconds(0.05, TimerMode::Repeating)),
-                false,
-            ),
-            EnemyVariant::Skelton => (
-                ENEMY2_DEAD_SPRITE,
-                game_textures.enemy_skeleton_dead.clone(),
-                EnemyVariant::Skelton,
-                AnimationIndices { first: 0, last: 14 },
-                AnimationTimer(Timer::from_seconds(0.03, TimerMode::Repeating)),
-                true,
-            ),
-            EnemyVariant::Goblin => (
-                ENEMY3_DEAD_SPRITE,
-                game_textures.enemy_goblin_dead.clone(),
-                EnemyVariant::Goblin,
-                AnimationIndices { first: 0, last: 3 },
-                AnimationTimer::default(),
-                true,
-            ),
+    for enemy_killed in enemy_killed_events.iter() {
+        // a reload may have taken the enemy out of the manifest
+        let Some(enemy_type) = enemy_types.types.get(&enemy_killed.variant.0) else {
+            continue;
         };
 
+        // spawn the dead enemy sprite
+        let enemy_sprite = sprite_registry.get(&enemy_type.death_sprite);
+        let death_clip = enemy_sprite.clip(&enemy_type.death_animation).once();
+
         commands.spawn({
             EnemyDeadBundle {
                 entity: EnemyDead,
-                variant: dead_enemy_variant,
-                animation_indices: animation_indices,
-                animation_timer: animation_timer,
+                variant: enemy_killed.variant.clone(),
+                animation: AnimationController::new("die", death_clip.clone()),
                 movable: Movable { auto_despawn: true },
                 velocity: Velocity {
                     x: SCROLL_X_VELOCITY,
                     y: SCROLL_Y_VELOCITY,
                 },
+                interpolated: Interpolated::default(),
                 sprite_sheet: SpriteSheetBundle {
-                    texture_atlas: enemy_texture,
-                    sprite: TextureAtlasSprite::new(animation_indices.first),
+                    texture_atlas: enemy_sprite.atlas(),
+                    sprite: TextureAtlasSprite::new(death_clip.first),
                     transform: Transform {
-                        translation: Vec3::new(
-                            enemy_dead_location.x,
-                            enemy_dead_location.y,
-                            enemy_dead_location.z,
-                        ),
-                        scale: Vec3::splat(enemy_sprite.scale),
-                        rotation: if flip_x {
-                            Quat::from_rotation_y(std::f32::consts::PI)
-                        } else {
-                            Quat::IDENTITY
-                        },
-                        ..Default::default()
+                        translation: enemy_killed.position.truncate().extend(0.0),
+                        scale: Vec3::splat(enemy_sprite.sheet.scale),
+                        rotation: enemy_sprite.rotation(),
                     },
                     ..Default::default()
                 },
             }
         });
-
-        // despawn the EnemyDeadToSpawn
-        commands.entity(enemy_dead_entity).despawn();
     }
 }
 
 pub fn enemy_dead_movement_system(
     mut commands: Commands,
-    time: Res<Time>,
-    mut enemy_query: Query<
-        (
-            Entity,
-            &mut AnimationTimer,
-            &mut TextureAtlasSprite,
-            &AnimationIndices,
-            &Velocity,
-            &mut Transform,
-            &Movable,
-        ),
-        With<EnemyDead>,
-    >,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut enemy_query: Query<(Entity, &Velocity, &mut Transform, &Movable), With<EnemyDead>>,
+    playfield: Res<Playfield>,
 ) {
-    let window = window_query.get_single().unwrap();
-
-    for (
-        dead_enemy_entity,
-        mut timer,
-        mut sprite,
-        animation_indices,
-        velocity,
-        mut enemy_transform,
-        movable,
-    ) in enemy_query.iter_mut()
-    {
-        animate_sprite_single(&mut sprite, &animation_indices, &mut timer, &time);
-
+    for (dead_enemy_entity, velocity, mut enemy_transform, movable) in enemy_query.iter_mut() {
         let enemy_translation = &mut enemy_transform.translation;
         enemy_translation.x -= velocity.x * TIME_STEP * BASE_SPEED / 2.0 + 1.0;
 
         if movable.auto_despawn {
             // despawn when out of screen
-            let window_margin = -window.width() / 2.0 - 20.0;
+            let window_margin = -playfield.width / 2.0 - 20.0;
             if enemy_translation.x < window_margin {
                 commands.entity(dead_enemy_entity).despawn();
             }
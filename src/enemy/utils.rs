@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::animation::components::AnimationController;
+use crate::common::components::{Health, Interpolated, Movable, Velocity, Vitality};
+use crate::common::resources::SpriteRegistry;
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::assets::EnemyBehaviour;
+use crate::enemy::bundles::{BoneBundle, EnemyBundle};
+use crate::enemy::components::{
+    Bone, ChargeState, Charger, Elite, Enemy, EnemyVariant, Shambler, Skirmisher,
+};
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::{
+    BONE_COLOR, ELITE_COLOR, ELITE_HEALTH_MULTIPLIER, ELITE_SCALE, ELITE_SPEED_MULTIPLIER,
+};
+
+/// The point in `targets` closest to `translation`, ignoring depth.
+pub fn nearest_target(translation: Vec3, targets: &[Vec3]) -> Option<Vec3> {
+    targets.iter().copied().min_by(|a, b| {
+        let distance_a = a.truncate().distance_squared(translation.truncate());
+        let distance_b = b.truncate().distance_squared(translation.truncate());
+        distance_a.total_cmp(&distance_b)
+    })
+}
+
+/// Move `from` toward `to` by at most `step`, without overshooting.
+pub fn approach(from: f32, to: f32, step: f32) -> f32 {
+    if (to - from).abs() <= step {
+        to
+    } else {
+        from + step * (to - from).signum()
+    }
+}
+
+/// Spawn an enemy of the type named `enemy_key` at `position`, with its
+/// behaviour and a speed picked from the type's range. Its speed and health
+/// are scaled by the current `difficulty`, which may also make it an elite.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    sprite_registry: &SpriteRegistry,
+    enemy_types: &EnemyTypes,
+    difficulty: &Difficulty,
+    enemy_key: &str,
+    position: Vec2,
+    rng: &mut impl Rng,
+) -> Entity {
+    let enemy_type = enemy_types.get(enemy_key);
+
+    let enemy_sprite = sprite_registry.get(&enemy_type.sprite);
+    let walk_clip = enemy_sprite.clip(&enemy_type.walk_animation);
+    let hurt_clip = sprite_registry
+        .get(&enemy_type.death_sprite)
+        .clip(&enemy_type.hurt_animation)
+        .once();
+
+    let elite = rng.gen_bool(difficulty.elite_chance as f64);
+    let (speed_scale, health_scale, size_scale, color) = match elite {
+        true => (
+            difficulty.enemy_speed * ELITE_SPEED_MULTIPLIER,
+            difficulty.enemy_health * ELITE_HEALTH_MULTIPLIER,
+            ELITE_SCALE,
+            ELITE_COLOR,
+        ),
+        false => (
+            difficulty.enemy_speed,
+            difficulty.enemy_health,
+            1.0,
+            Color::WHITE,
+        ),
+    };
+
+    let mut enemy = commands.spawn(EnemyBundle {
+        entity: Enemy,
+        variant: EnemyVariant(enemy_key.to_string()),
+        vitality: Vitality::Alive,
+        health: Health::new((enemy_type.health as f32 * health_scale).round().max(1.0) as u32),
+        collider: enemy_type.collider,
+        animation: AnimationController::new("walk", walk_clip.clone()).with_clip("hurt", hurt_clip),
+        movable: Movable { auto_despawn: true },
+        velocity: Velocity {
+            x: rng.gen_range(enemy_type.speed.min..=enemy_type.speed.max) * speed_scale,
+            y: rng.gen_range(enemy_type.speed.min..=enemy_type.speed.max) * speed_scale,
+        },
+        interpolated: Interpolated::default(),
+        sprite_sheet: SpriteSheetBundle {
+            texture_atlas: enemy_sprite.atlas(),
+            sprite: TextureAtlasSprite {
+                index: walk_clip.first,
+                color,
+                ..Default::default()
+            },
+            transform: Transform {
+                translation: position.extend(10.0),
+                rotation: enemy_sprite.rotation(),
+                scale: Vec3::splat(enemy_sprite.sheet.scale * size_scale),
+            },
+            ..Default::default()
+        },
+    });
+
+    if elite {
+        enemy.insert(Elite);
+    }
+
+    match enemy_type.behaviour {
+        EnemyBehaviour::Shamble { homing } => enemy.insert(Shambler { homing }),
+        EnemyBehaviour::KeepDistance {
+            distance,
+            throw_interval,
+            bone_speed,
+            bones,
+        } => enemy.insert(Skirmisher {
+            distance,
+            throw_timer: Timer::from_seconds(throw_interval, TimerMode::Repeating),
+            bone_speed,
+            bones,
+        }),
+        EnemyBehaviour::Charge {
+            range,
+            wind_up,
+            speed,
+        } => enemy.insert(Charger {
+            range,
+            speed: speed * speed_scale,
+            wind_up: Timer::from_seconds(wind_up, TimerMode::Once),
+            state: ChargeState::Approach,
+        }),
+    };
+
+    enemy.id()
+}
+
+/// Throw a bone from `position` along the unit vector `direction`.
+pub fn spawn_bone(
+    commands: &mut Commands,
+    sprite_registry: &SpriteRegistry,
+    position: Vec3,
+    direction: Vec2,
+    speed: f32,
+) {
+    let bone_sprite = sprite_registry.get("bullet");
+
+    commands.spawn(BoneBundle {
+        entity: Bone,
+        collider: bone_sprite.collider(),
+        velocity: Velocity {
+            x: direction.x * speed,
+            y: direction.y * speed,
+        },
+        interpolated: Interpolated::default(),
+        sprite_sheet: SpriteSheetBundle {
+            texture_atlas: bone_sprite.atlas(),
+            sprite: TextureAtlasSprite {
+                index: bone_sprite.animation("bone").first,
+                color: BONE_COLOR,
+                ..Default::default()
+            },
+            transform: Transform {
+                translation: position.truncate().extend(9.0),
+                rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+                scale: Vec3::splat(bone_sprite.sheet.scale),
+            },
+            ..Default::default()
+        },
+    });
+}
@@ -10,3 +10,8 @@ pub struct ColorText;
 
 #[derive(Component)]
 pub struct UiPlayerLives;
+
+/// Shows how long one player, the `PlayerVariant` beside it, stays
+/// invincible for.
+#[derive(Component)]
+pub struct InvincibleText;
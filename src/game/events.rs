@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::enemy::components::EnemyVariant;
+use crate::item::components::ItemVariant;
+use crate::player::components::PlayerVariant;
+
+/// What took an enemy out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillCause {
+    Fireball,
+    Diamond,
+}
+
+/// A player has hurt an enemy. Sent for every hit, including the one that
+/// kills it; `amount` is the hit points it actually lost.
+pub struct EnemyDamaged {
+    pub variant: EnemyVariant,
+    pub attacker: PlayerVariant,
+    pub amount: u32,
+    pub position: Vec3,
+}
+
+/// A player has killed an enemy.
+pub struct EnemyKilled {
+    pub variant: EnemyVariant,
+    pub killer: PlayerVariant,
+    pub cause: KillCause,
+    /// The enemy was an elite, worth extra points.
+    pub elite: bool,
+    pub position: Vec3,
+}
+
+/// A player has beaten the boss.
+pub struct BossDefeated {
+    pub killer: PlayerVariant,
+    pub position: Vec3,
+}
+
+/// A player's shield has taken a hit for them.
+pub struct PlayerHurt {
+    pub player: PlayerVariant,
+}
+
+/// A player has lost a life.
+pub struct PlayerDied {
+    pub player: PlayerVariant,
+    pub position: Vec3,
+}
+
+/// A player has picked up a power-up.
+pub struct PowerUpCollected {
+    pub player: PlayerVariant,
+    pub variant: ItemVariant,
+    pub position: Vec3,
+}
+
+/// A player has fired.
+pub struct ShotFired {
+    pub player: PlayerVariant,
+    pub position: Vec3,
+}
+
+/// A new wave has started. Waves count from 1.
+pub struct WaveStarted {
+    pub number: u32,
+}
+
+/// A wave's clear condition has been met.
+pub struct WaveCleared {
+    pub number: u32,
+}
@@ -1,31 +1,137 @@
 pub mod components;
+pub mod events;
+pub mod resources;
 pub mod states;
 pub mod systems;
 
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::ecs::schedule::ExecutorKind;
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
 
+use crate::common::assets::SpriteManifest;
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry, SpriteSheets};
+use crate::common::systems::{
+    interpolation_capture_system, interpolation_render_system, interpolation_restore_system,
+    sprite_registry_system,
+};
+use crate::common::{ManifestPlugin, TickSet, TIME_STEP};
+use crate::difficulty::resources::DifficultyPresets;
+use crate::enemy::resources::EnemyTypes;
+use crate::game::events::{
+    BossDefeated, EnemyDamaged, EnemyKilled, PlayerDied, PlayerHurt, PowerUpCollected, ShotFired,
+    WaveCleared, WaveStarted,
+};
+use crate::game::resources::{GameDataLoaded, GameMode};
 use crate::game::states::{AppState, GameState};
 use crate::game::systems::{
-    camera_spawn_system, exit_game_system, game_over_system, player_lives_despawn_system,
-    player_lives_spawn_system, restart_game_system, score_spawn_system, score_update_system,
-    toggle_game_state_system,
+    camera_spawn_system, exit_game_system, game_audio_system, game_data_loaded_system,
+    game_over_system, invincible_text_update_system, player_lives_despawn_system,
+    player_lives_spawn_system, playfield_sync_system, restart_game_system, score_spawn_system,
+    score_update_system, toggle_game_state_system,
 };
+use crate::item::resources::ItemTypes;
+use crate::item::ItemSystemSet;
+use crate::replay::resources::ReplayPlayback;
+use crate::score::resources::InitialsEntry;
+use crate::wave::resources::WaveScripts;
+use crate::weapon::resources::WeaponTypes;
+
+/// Systems that react to gameplay events (sound, score, corpses, effects),
+/// once every gameplay set has run for the tick.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GameEventSystemSet;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugin(FrameTimeDiagnosticsPlugin::default())
+        app.add_plugin(FrameTimeDiagnosticsPlugin)
             .add_state::<AppState>()
+            .init_resource::<GameMode>()
+            .init_resource::<Playfield>()
+            .init_resource::<GameRng>()
+            .insert_resource(FixedTime::new_from_secs(TIME_STEP))
+            .add_event::<EnemyDamaged>()
+            .add_event::<EnemyKilled>()
+            .add_event::<BossDefeated>()
+            .add_event::<PlayerHurt>()
+            .add_event::<PlayerDied>()
+            .add_event::<PowerUpCollected>()
+            .add_event::<ShotFired>()
+            .add_event::<WaveStarted>()
+            .add_event::<WaveCleared>()
+            .add_plugin(ManifestPlugin::<SpriteManifest>::default())
+            .add_system(
+                sprite_registry_system
+                    .in_base_set(CoreSet::First)
+                    .run_if(resource_exists::<SpriteSheets>()),
+            )
+            .add_system(
+                game_data_loaded_system
+                    .in_base_set(CoreSet::First)
+                    .after(sprite_registry_system)
+                    .run_if(not(resource_exists::<GameDataLoaded>()))
+                    .run_if(resource_exists::<SpriteRegistry>())
+                    .run_if(resource_exists::<EnemyTypes>())
+                    .run_if(resource_exists::<WaveScripts>())
+                    .run_if(resource_exists::<DifficultyPresets>())
+                    .run_if(resource_exists::<ItemTypes>())
+                    .run_if(resource_exists::<WeaponTypes>()),
+            )
+            // Nothing is simulated until the game data it runs on has loaded.
+            .configure_set(CoreSet::FixedUpdate.run_if(resource_exists::<GameDataLoaded>()))
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                // Run the simulation in the same order every tick, so a seed
+                // and a set of inputs always play out the same way.
+                schedule
+                    .set_executor_kind(ExecutorKind::SingleThreaded)
+                    .configure_set(TickSet::Begin.before(TickSet::End))
+                    .configure_set(
+                        GameEventSystemSet
+                            .run_if(in_state(GameState::Playing))
+                            .after(ItemSystemSet)
+                            .after(TickSet::Begin)
+                            .before(TickSet::End),
+                    );
+            })
+            .add_system(
+                interpolation_restore_system
+                    .in_set(TickSet::Begin)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                game_audio_system
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                interpolation_capture_system
+                    .in_set(TickSet::End)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                interpolation_render_system
+                    .in_base_set(CoreSet::PostUpdate)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_startup_system(playfield_sync_system.in_base_set(StartupSet::PreStartup))
+            .add_system(playfield_sync_system.in_base_set(CoreSet::First))
             .add_startup_system(camera_spawn_system)
             .add_startup_system(score_spawn_system)
             .add_system(score_update_system)
-            .add_system(toggle_game_state_system)
+            .add_system(invincible_text_update_system)
+            .add_system(toggle_game_state_system.run_if(not(resource_exists::<ReplayPlayback>())))
             .add_system(exit_game_system)
-            .add_system(game_over_system)
-            .add_system(player_lives_spawn_system)
+            .add_system(game_over_system.run_if(in_state(GameState::Playing)))
+            .add_system(player_lives_spawn_system.run_if(resource_exists::<GameDataLoaded>()))
             .add_system(player_lives_despawn_system)
-            .add_system(restart_game_system.run_if(in_state(GameState::GameOver)));
+            .add_system(
+                restart_game_system
+                    .run_if(in_state(GameState::GameOver).or_else(in_state(GameState::Victory)))
+                    .run_if(resource_exists::<GameDataLoaded>())
+                    // R would restart while it is being typed into initials
+                    .run_if(not(resource_exists::<InitialsEntry>())),
+            );
     }
 }
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Inserted once every data asset the game needs (sprites, enemy types,
+/// waves, difficulty curves, weapons) has loaded. Gameplay waits for it.
+#[derive(Resource)]
+pub struct GameDataLoaded;
+
+/// Whether one player or both take part in a game.
+#[derive(
+    Resource,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub enum GameMode {
+    Solo,
+    #[default]
+    Coop,
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Solo => "Solo",
+            GameMode::Coop => "Co-op",
+        }
+    }
+}
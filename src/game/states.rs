@@ -13,4 +13,6 @@ pub enum GameState {
     Playing,
     #[default]
     GameOver,
+    /// The boss has been beaten.
+    Victory,
 }
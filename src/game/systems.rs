@@ -1,21 +1,55 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_kira_audio::prelude::Audio;
+use bevy_kira_audio::{AudioControl, AudioTween};
 use leafwing_input_manager::prelude::ActionState;
 
 use crate::common::components::Vitality;
-use crate::common::resources::GameTextures;
-use crate::enemy::components::Enemy;
-use crate::game::components::ColorText;
+use crate::common::resources::{GameAudio, GameRng, Playfield, SpriteRegistry};
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::components::{Bone, Enemy};
+use crate::game::components::{ColorText, InvincibleText};
+use crate::game::events::{BossDefeated, EnemyKilled, PlayerDied, PowerUpCollected, ShotFired};
+use crate::game::resources::{GameDataLoaded, GameMode};
 use crate::game::states::{AppState, GameState};
+use crate::item::assets::Effect;
+use crate::item::components::{ActiveEffects, PowerUp};
 use crate::player::actions::ControlAction;
-use crate::player::components::{Lives, Player, PlayerVariant, Score};
+use crate::player::components::{Fireball, Lives, Player, PlayerVariant, Score};
+use crate::wave::resources::WaveDirector;
+use crate::weapon::components::Weapon;
+use crate::weapon::resources::WeaponTypes;
+use crate::weapon::STARTING_WEAPON;
 
 use super::components::UiPlayerLives;
 
+pub fn game_data_loaded_system(mut commands: Commands) {
+    info!("Game data loaded");
+    commands.insert_resource(GameDataLoaded);
+}
+
 pub fn camera_spawn_system(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
+/// Keep the playfield the same size as the primary window, if there is one.
+pub fn playfield_sync_system(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut playfield: ResMut<Playfield>,
+) {
+    if !playfield.follow_window {
+        return;
+    }
+
+    if let Ok(window) = window_query.get_single() {
+        if playfield.width != window.width() || playfield.height != window.height() {
+            playfield.width = window.width();
+            playfield.height = window.height();
+        }
+    }
+}
+
 pub fn toggle_game_state_system(
     keyboard_input: Res<Input<KeyCode>>,
     controller_query: Query<&ActionState<ControlAction>>,
@@ -24,7 +58,7 @@ pub fn toggle_game_state_system(
     mut app_state_next_state: ResMut<NextState<AppState>>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
 ) {
-    if game_state.0 != GameState::GameOver {
+    if matches!(game_state.0, GameState::Playing | GameState::Paused) {
         for controller_input in controller_query.iter() {
             if keyboard_input.just_pressed(KeyCode::Space)
                 || controller_input.just_pressed(ControlAction::Pause)
@@ -49,15 +83,17 @@ pub fn game_over_system(
     mut app_state_next_state: ResMut<NextState<AppState>>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
 ) {
+    let mut players = 0;
     let mut dead_players = 0;
 
     for (player_vitality, player_lives) in player_query.iter() {
+        players += 1;
         if *player_vitality == Vitality::Dead && player_lives.count == 0 {
             dead_players += 1;
         }
     }
 
-    if dead_players == 2 {
+    if players > 0 && dead_players == players {
         game_state_next_state.set(GameState::GameOver);
         app_state_next_state.set(AppState::MainMenu);
     }
@@ -72,6 +108,7 @@ pub fn exit_game_system(
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn restart_game_system(
     mut commands: Commands,
     mut player_query: Query<
@@ -80,14 +117,19 @@ pub fn restart_game_system(
             &mut Vitality,
             &mut Lives,
             &mut Score,
-            &mut Handle<TextureAtlas>,
+            &mut Weapon,
+            &mut ActiveEffects,
             &ActionState<ControlAction>,
         ),
         With<Player>,
     >,
-    enemy_query: Query<Entity, With<Enemy>>,
+    // loot drops are power-ups too
+    leftover_query: Query<Entity, Or<(With<Enemy>, With<PowerUp>, With<Fireball>, With<Bone>)>>,
+    mut wave_director: ResMut<WaveDirector>,
+    mut difficulty: ResMut<Difficulty>,
+    mut game_rng: ResMut<GameRng>,
+    weapon_types: Res<WeaponTypes>,
     keyboard_input: Res<Input<KeyCode>>,
-    game_textures: Res<GameTextures>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
@@ -97,7 +139,8 @@ pub fn restart_game_system(
         _player_vitality,
         _player_lives,
         _player_score,
-        _player_sprite,
+        _player_weapon,
+        _player_active_effects,
         controller_input,
     ) in player_query.iter()
     {
@@ -110,29 +153,29 @@ pub fn restart_game_system(
     }
 
     if restart_game {
-        for enemy_entity in enemy_query.iter() {
-            commands.entity(enemy_entity).despawn();
+        for leftover_entity in leftover_query.iter() {
+            commands.entity(leftover_entity).despawn();
         }
+        *wave_director = WaveDirector::default();
+        *difficulty = Difficulty::default();
+        // every game on the same seed plays out the same way
+        *game_rng = GameRng::new(game_rng.seed);
 
         for (
-            player_variant,
+            _player_variant,
             mut player_vitality,
             mut player_lives,
             mut player_score,
-            mut player_sprite,
+            mut player_weapon,
+            mut player_active_effects,
             _controller_input,
         ) in player_query.iter_mut()
         {
-            let player_sprite_atlas = match player_variant {
-                PlayerVariant::One => game_textures.player_one.clone(),
-                PlayerVariant::Two => game_textures.player_two.clone(),
-            };
-
-            *player_sprite = player_sprite_atlas;
-
             *player_vitality = Vitality::Alive;
             player_lives.count = 3;
             player_score.value = 0;
+            *player_weapon = Weapon::new(STARTING_WEAPON, weapon_types.get(STARTING_WEAPON));
+            *player_active_effects = ActiveEffects::default();
             game_state_next_state.set(GameState::Playing);
             app_state_next_state.set(AppState::InGame);
         }
@@ -141,7 +184,7 @@ pub fn restart_game_system(
 
 pub fn player_lives_spawn_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
+    sprite_registry: Res<SpriteRegistry>,
     player_query: Query<(&Lives, &PlayerVariant), With<Player>>,
     ui_player_lives: Query<&PlayerVariant, With<UiPlayerLives>>,
 ) {
@@ -157,8 +200,14 @@ pub fn player_lives_spawn_system(
 
     for (player_lives, player_variant) in player_query.iter() {
         let (player_ui_lives, player_ui_sprite) = match &player_variant {
-            PlayerVariant::One => (player1_ui_lives, game_textures.player_one_static.clone()),
-            PlayerVariant::Two => (player2_ui_lives, game_textures.player_two_static.clone()),
+            PlayerVariant::One => (
+                player1_ui_lives,
+                sprite_registry.get("player_one_static").image.clone(),
+            ),
+            PlayerVariant::Two => (
+                player2_ui_lives,
+                sprite_registry.get("player_two_static").image.clone(),
+            ),
         };
 
         if player_ui_lives < player_lives.count {
@@ -187,7 +236,7 @@ pub fn player_lives_spawn_system(
                                 top: Val::Px(65.0),
                                 bottom: Val::Px(8.0),
                             },
-                            position: position,
+                            position,
                             ..Style::DEFAULT
                         },
                         image: player_ui_sprite.clone().into(),
@@ -231,7 +280,11 @@ pub fn player_lives_despawn_system(
     }
 }
 
-pub fn score_spawn_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn score_spawn_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+) {
     // 0
 
     commands.spawn((
@@ -264,6 +317,47 @@ pub fn score_spawn_system(mut commands: Commands, asset_server: Res<AssetServer>
         PlayerVariant::One,
     ));
 
+    // under each player's lives
+    let invincible_text = |position: UiRect| {
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 40.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position,
+            ..default()
+        })
+    };
+    commands.spawn((
+        invincible_text(UiRect {
+            top: Val::Px(125.0),
+            left: Val::Px(15.0),
+            ..default()
+        }),
+        InvincibleText,
+        PlayerVariant::One,
+    ));
+
+    if *game_mode == GameMode::Solo {
+        return;
+    }
+
+    commands.spawn((
+        invincible_text(UiRect {
+            top: Val::Px(125.0),
+            right: Val::Px(15.0),
+            ..default()
+        }),
+        InvincibleText,
+        PlayerVariant::Two,
+    ));
+
     // 1
     commands.spawn((
         // Create a TextBundle that has a Text with a single section.
@@ -292,6 +386,40 @@ pub fn score_spawn_system(mut commands: Commands, asset_server: Res<AssetServer>
     ));
 }
 
+/// Play the sound for everything that happened during the tick.
+pub fn game_audio_system(
+    mut shot_events: EventReader<ShotFired>,
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+    mut boss_defeated_events: EventReader<BossDefeated>,
+    mut player_died_events: EventReader<PlayerDied>,
+    mut power_up_events: EventReader<PowerUpCollected>,
+    game_audio: Res<GameAudio>,
+    audio: Res<Audio>,
+) {
+    for _ in shot_events.iter() {
+        audio.play(game_audio.player_shoot.clone());
+    }
+
+    for _ in enemy_killed_events.iter() {
+        audio.play(game_audio.enemy_dead.clone());
+    }
+
+    for _ in boss_defeated_events.iter() {
+        audio.play(game_audio.enemy_dead.clone());
+    }
+
+    for _ in player_died_events.iter() {
+        audio.play(game_audio.player_dead.clone());
+    }
+
+    for _ in power_up_events.iter() {
+        audio
+            .play(game_audio.diamond_powerup.clone())
+            .with_volume(0.5)
+            .fade_in(AudioTween::default());
+    }
+}
+
 pub fn score_update_system(
     player_query: Query<(&PlayerVariant, &Score), With<Player>>,
     mut query: Query<(&mut Text, &PlayerVariant), With<ColorText>>,
@@ -304,3 +432,22 @@ pub fn score_update_system(
         }
     }
 }
+
+/// Count down the seconds of invincibility each player has left, under
+/// their own lives, while they are invincible.
+pub fn invincible_text_update_system(
+    player_query: Query<(&PlayerVariant, &ActiveEffects), With<Player>>,
+    mut query: Query<(&mut Text, &PlayerVariant), With<InvincibleText>>,
+) {
+    for (mut text, text_player_variant) in query.iter_mut() {
+        let remaining = player_query
+            .iter()
+            .find(|(player_variant, _)| *player_variant == text_player_variant)
+            .and_then(|(_, active_effects)| active_effects.remaining_secs(Effect::Invincible));
+
+        text.sections[0].value = match remaining {
+            Some(remaining) => format!("Invincible {:.0}", remaining.ceil()),
+            None => String::new(),
+        };
+    }
+}
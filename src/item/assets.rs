@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::Color;
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::item::resources::ItemTypes;
+use crate::item::{ITEM_MANIFEST, MAX_EFFECT_DURATION};
+
+/// Every power-up that turns up on the playfield, keyed by name. Loaded from
+/// `assets/manifest.items.ron`, so new items need no code changes as long as
+/// their `effect` already exists.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "5a0d7e3c-94b1-4f26-8c6a-1e2b9f47d815"]
+pub struct ItemManifest {
+    pub items: BTreeMap<String, ItemType>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ItemType {
+    /// `SpriteRegistry` key of the sheet the item is drawn with.
+    pub sprite: String,
+    /// Animation of the `sprite` sheet looped while the item lies around.
+    pub animation: String,
+    /// Tints the sprite.
+    #[serde(default = "default_color")]
+    pub color: Color,
+    /// The `sprite` sheet's own scale if not given.
+    #[serde(default)]
+    pub scale: Option<f32>,
+    /// Pickup radius, in pixels of the `sprite` sheet. The sheet's own
+    /// collider if not given.
+    #[serde(default)]
+    pub pickup_radius: Option<f32>,
+    /// How likely the item is to be picked, relative to the others that
+    /// could turn up. 0 for items that only ever drop as loot.
+    #[serde(default)]
+    pub spawn_weight: f32,
+    #[serde(default)]
+    pub conditions: SpawnConditions,
+    /// What the item does for the player who picks it up, if anything
+    /// besides its `score`.
+    #[serde(default)]
+    pub effect: Option<Effect>,
+    /// Points for picking it up.
+    #[serde(default)]
+    pub score: u32,
+    /// Seconds the effect lasts for. Unused by effects that happen at once.
+    #[serde(default)]
+    pub duration: f32,
+    #[serde(default)]
+    pub stacking: EffectStacking,
+}
+
+/// When an item may turn up.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct SpawnConditions {
+    /// Seconds into the game before it can.
+    pub after: f32,
+    /// How many of it can lie around at once.
+    pub max_on_field: u32,
+    /// Not while any player already has its effect.
+    pub unless_active: bool,
+}
+
+impl Default for SpawnConditions {
+    fn default() -> Self {
+        Self {
+            after: 0.0,
+            max_on_field: 1,
+            unless_active: false,
+        }
+    }
+}
+
+/// A power a player gets from an item. Each is handled by the systems it
+/// changes the outcome of.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Effect {
+    /// Kills enemies on contact.
+    Diamond,
+    /// Enemies and bones do the player no harm.
+    Invincible,
+    /// The player moves `SPEED_BOOST_MULTIPLIER` times faster.
+    Speed,
+    /// Takes the next hit for the player, who is then invincible for
+    /// `SHIELD_GRACE_TIME` seconds.
+    Shield,
+    /// One more life, at once.
+    ExtraLife,
+}
+
+/// What picking up an effect the player already has does to it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EffectStacking {
+    /// Start it over, at its full duration.
+    #[default]
+    Refresh,
+    /// Add its full duration to what is left of it.
+    Extend,
+}
+
+impl ItemType {
+    /// What, if anything, stops the item from being put down or picked up.
+    fn check(&self) -> Result<(), String> {
+        // effects last for `Duration::from_secs_f32(duration)`
+        if !self.duration.is_finite() || self.duration < 0.0 {
+            return Err(format!("lasts for {} seconds", self.duration));
+        }
+        if !self.spawn_weight.is_finite() || self.spawn_weight < 0.0 {
+            return Err(format!("has a spawn_weight of {}", self.spawn_weight));
+        }
+        if !self.conditions.after.is_finite() {
+            return Err(format!("turns up after {} seconds", self.conditions.after));
+        }
+        Ok(())
+    }
+}
+
+fn default_color() -> Color {
+    Color::WHITE
+}
+
+impl RonManifest for ItemManifest {
+    const PATH: &'static str = ITEM_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["items.ron"];
+}
+
+impl ManifestResource for ItemManifest {
+    type Resource = ItemTypes;
+
+    fn build(&self) -> Result<ItemTypes, String> {
+        for (key, item) in self.items.iter() {
+            item.check()
+                .map_err(|problem| format!("item \"{}\" {}", key, problem))?;
+        }
+
+        let mut types = self.items.clone();
+        for item in types.values_mut() {
+            item.duration = item.duration.min(MAX_EFFECT_DURATION);
+        }
+
+        Ok(ItemTypes { types })
+    }
+}
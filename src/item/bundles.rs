@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use crate::common::components::{AnimationIndices, AnimationTimer, Movable, Velocity};
+use crate::animation::components::AnimationController;
+use crate::collision::components::Collider;
+use crate::common::components::{Movable, Velocity};
 
 use crate::item::components::{ItemVariant, PowerUp};
 
@@ -8,8 +10,8 @@ use crate::item::components::{ItemVariant, PowerUp};
 pub struct PowerUpBundle {
     pub power_up: PowerUp,
     pub variant: ItemVariant,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
+    pub collider: Collider,
+    pub animation: AnimationController,
     pub movable: Movable,
     pub velocity: Velocity,
 
@@ -1,24 +1,88 @@
-use bevy::prelude::Component;
+use std::collections::BTreeMap;
+use std::time::Duration;
 
-/// When consumed, a mushroom makes the player
-/// invincible for 30 seconds.
-#[derive(Component)]
-pub struct Mushroom;
+use bevy::prelude::*;
 
-#[derive(Component)]
-pub struct Diamond;
+use crate::item::assets::{Effect, EffectStacking};
 
 #[derive(Component)]
 pub struct PowerUp;
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub enum ItemVariant {
-    Diamond,
-    Mushroom,
+    /// Gives the player the effect of the `ItemTypes` entry of this key.
+    Item(String),
+    /// Swaps the player's weapon for the one in its `WeaponPickup`.
+    Weapon,
+}
+
+/// An item an enemy left behind. It scrolls away with the world, and is gone
+/// once `timer` runs out if nobody picks it up.
+#[derive(Component, Debug)]
+pub struct LootDrop {
+    pub timer: Timer,
+}
+
+/// The effects a player has running, each with its own timer.
+#[derive(Component, Debug, Default)]
+pub struct ActiveEffects {
+    timers: BTreeMap<Effect, Timer>,
 }
 
-#[derive(Component, Default)]
-pub struct ItemPower {
-    pub diamond: bool,
-    pub mushroom: bool,
+impl ActiveEffects {
+    pub fn is_active(&self, effect: Effect) -> bool {
+        self.timers.contains_key(&effect)
+    }
+
+    /// The timer of `effect`, if it is running.
+    pub fn timer(&self, effect: Effect) -> Option<&Timer> {
+        self.timers.get(&effect)
+    }
+
+    /// Seconds `effect` has left, if it is running.
+    pub fn remaining_secs(&self, effect: Effect) -> Option<f32> {
+        self.timer(effect).map(Timer::remaining_secs)
+    }
+
+    /// Start `effect` for `duration` seconds, or stack it onto the one
+    /// already running.
+    pub fn grant(&mut self, effect: Effect, duration: f32, stacking: EffectStacking) {
+        let duration = Duration::from_secs_f32(duration);
+
+        match self.timers.get_mut(&effect) {
+            Some(timer) => match stacking {
+                EffectStacking::Refresh => {
+                    timer.set_duration(duration);
+                    timer.reset();
+                }
+                EffectStacking::Extend => timer.set_duration(timer.duration() + duration),
+            },
+            None => {
+                self.timers
+                    .insert(effect, Timer::new(duration, TimerMode::Once));
+            }
+        }
+    }
+
+    /// End `effect` early. Returns whether it was running.
+    pub fn remove(&mut self, effect: Effect) -> bool {
+        self.timers.remove(&effect).is_some()
+    }
+
+    /// Run every effect's timer on by `delta`, and drop the ones that ran
+    /// out. Returns the effects that wore off.
+    pub fn tick(&mut self, delta: Duration) -> Vec<Effect> {
+        let mut expired = Vec::new();
+
+        for (effect, timer) in self.timers.iter_mut() {
+            if timer.tick(delta).finished() {
+                expired.push(*effect);
+            }
+        }
+        for effect in expired.iter() {
+            self.timers.remove(effect);
+        }
+
+        expired
+    }
 }
@@ -1,31 +1,41 @@
+pub mod assets;
 pub mod bundles;
 pub mod components;
 pub mod resources;
 pub mod systems;
+pub mod utils;
 
 use bevy::prelude::*;
 
-use crate::common::SpriteSheet;
-
-use crate::item::resources::{DiamondPowerTimer, DiamondSpawnTimer};
+use crate::common::ManifestPlugin;
+use crate::item::assets::ItemManifest;
+use crate::item::resources::ItemSpawnTimer;
 use crate::item::systems::{
-    diamond_power_timer_tick_system, diamond_spawn_system, diamond_spawn_timer_tick_system,
-    power_up_animation_system,
+    enemy_loot_drop_system, item_spawn_system, item_spawn_timer_tick_system,
+    item_sprites_check_system, loot_drop_movement_system, loot_table_check_system,
 };
 
+use crate::common::TickSet;
+use crate::enemy::EnemySystemSet;
+use crate::game::resources::GameDataLoaded;
 use crate::game::states::GameState;
+use crate::game::GameEventSystemSet;
 
-pub const DIAMOND_SPRITE: SpriteSheet = SpriteSheet {
-    file: "diamond.png",
-    width: 18.0,
-    height: 14.0,
-    scale: 3.0,
-    columns: 10,
-    rows: 1,
-};
-
-pub const DIAMOND_SPAWN_TIME: f32 = 10.0;
-pub const DIAMOND_POWER_TIME: f32 = 60.0;
+pub const ITEM_MANIFEST: &str = "manifest.items.ron";
+pub const ITEM_SPAWN_TIME: f32 = 10.0;
+/// How long an invincible player spends shown, then hidden, while blinking.
+pub const INVINCIBLE_BLINK_TIME: f32 = 0.1;
+pub const SPEED_BOOST_MULTIPLIER: f32 = 1.5;
+/// Seconds dropped loot lies around before it is gone.
+pub const LOOT_DROP_TIME: f32 = 8.0;
+/// How far, in pixels, loot lands from where its enemy died.
+pub const LOOT_SCATTER: f32 = 20.0;
+/// The longest an item's effect lasts, in seconds. Longer durations in the
+/// item manifest are cut down to it.
+pub const MAX_EFFECT_DURATION: f32 = 3600.0;
+/// Seconds of invincibility a shield leaves behind once it takes a hit, to
+/// get clear of whatever hit it.
+pub const SHIELD_GRACE_TIME: f32 = 1.5;
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct ItemSystemSet;
@@ -33,17 +43,37 @@ pub struct ItemPlugin;
 
 impl Plugin for ItemPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DiamondSpawnTimer>()
-            .init_resource::<DiamondPowerTimer>()
-            .configure_set(ItemSystemSet.run_if(in_state(GameState::Playing)))
+        app.init_resource::<ItemSpawnTimer>()
+            .add_plugin(ManifestPlugin::<ItemManifest>::default())
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    ItemSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(EnemySystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
             .add_systems(
                 (
-                    diamond_spawn_system,
-                    diamond_spawn_timer_tick_system,
-                    power_up_animation_system,
-                    diamond_power_timer_tick_system,
+                    loot_table_check_system,
+                    item_spawn_system,
+                    item_spawn_timer_tick_system,
+                    loot_drop_movement_system,
                 )
-                    .in_set(ItemSystemSet),
+                    .chain()
+                    .in_set(ItemSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                item_sprites_check_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .run_if(resource_exists::<GameDataLoaded>()),
+            )
+            .add_system(
+                enemy_loot_drop_system
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
             );
     }
 }
@@ -1,30 +1,66 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
+use rand::Rng;
 
-use crate::item::DIAMOND_POWER_TIME;
-use crate::item::DIAMOND_SPAWN_TIME;
+use crate::item::assets::ItemType;
+use crate::item::{ITEM_MANIFEST, ITEM_SPAWN_TIME};
 
-#[derive(Resource)]
-pub struct DiamondSpawnTimer {
-    pub timer: Timer,
+/// The loaded item manifest. Exists once `assets/manifest.items.ron` has
+/// loaded.
+#[derive(Resource, Default)]
+pub struct ItemTypes {
+    pub types: BTreeMap<String, ItemType>,
 }
 
-impl Default for DiamondSpawnTimer {
-    fn default() -> Self {
-        Self {
-            timer: Timer::from_seconds(DIAMOND_SPAWN_TIME, TimerMode::Repeating),
+impl ItemTypes {
+    /// The item type named `key`. Panics if the manifest has no such type.
+    pub fn get(&self, key: &str) -> &ItemType {
+        self.types
+            .get(key)
+            .unwrap_or_else(|| panic!("no item named \"{}\" in {}", key, ITEM_MANIFEST))
+    }
+
+    /// Pick one of the items `allowed` lets through at random, weighted by
+    /// `spawn_weight`. `None` if none of them can turn up.
+    pub fn choose(
+        &self,
+        rng: &mut impl Rng,
+        allowed: impl Fn(&str, &ItemType) -> bool,
+    ) -> Option<&str> {
+        let candidates: Vec<(&str, f32)> = self
+            .types
+            .iter()
+            .filter(|(key, item)| item.spawn_weight > 0.0 && allowed(key, item))
+            .map(|(key, item)| (key.as_str(), item.spawn_weight))
+            .collect();
+
+        let total_weight: f32 = candidates.iter().map(|(_, weight)| weight).sum();
+        if total_weight <= 0.0 {
+            return None;
         }
+        let mut pick = rng.gen_range(0.0..total_weight);
+
+        for (key, weight) in candidates.iter() {
+            if pick < *weight {
+                return Some(key);
+            }
+            pick -= weight;
+        }
+
+        candidates.last().map(|(key, _)| *key)
     }
 }
 
 #[derive(Resource)]
-pub struct DiamondPowerTimer {
+pub struct ItemSpawnTimer {
     pub timer: Timer,
 }
 
-impl Default for DiamondPowerTimer {
+impl Default for ItemSpawnTimer {
     fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(DIAMOND_POWER_TIME, TimerMode::Once),
+            timer: Timer::from_seconds(ITEM_SPAWN_TIME, TimerMode::Repeating),
         }
     }
 }
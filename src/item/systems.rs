@@ -1,108 +1,200 @@
-use rand::Rng;
-
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
-use crate::common::components::{AnimationIndices, AnimationTimer, Movable, Velocity};
-use crate::common::resources::GameTextures;
-use crate::common::utils::animate_sprite;
+use rand::Rng;
+
+use crate::common::components::Movable;
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::ENEMY_MANIFEST;
+use crate::game::events::EnemyKilled;
 
-use crate::item::bundles::PowerUpBundle;
-use crate::item::components::{ItemPower, ItemVariant, PowerUp};
-use crate::item::resources::{DiamondPowerTimer, DiamondSpawnTimer};
+use crate::item::components::{ActiveEffects, ItemVariant, LootDrop, PowerUp};
+use crate::item::resources::{ItemSpawnTimer, ItemTypes};
+use crate::item::utils::{random_spawn_position, spawn_item};
+use crate::item::{ITEM_MANIFEST, LOOT_DROP_TIME, LOOT_SCATTER};
 
 use crate::player::components::Player;
-use crate::DIAMOND_SPRITE;
+use crate::world::GROUND_SCROLL_STEP;
 
-pub fn diamond_spawn_system(
+/// Every so often, put down a random item out of those whose spawn
+/// conditions hold.
+#[allow(clippy::too_many_arguments)]
+pub fn item_spawn_system(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    diamond_spawn_timer: Res<DiamondSpawnTimer>,
-    player_query: Query<&ItemPower, With<Player>>,
-    diamond_qeury: Query<&ItemVariant, With<PowerUp>>,
-    game_textures: Res<GameTextures>,
+    playfield: Res<Playfield>,
+    item_spawn_timer: Res<ItemSpawnTimer>,
+    difficulty: Res<Difficulty>,
+    player_query: Query<&ActiveEffects, With<Player>>,
+    power_up_query: Query<&ItemVariant, With<PowerUp>>,
+    sprite_registry: Res<SpriteRegistry>,
+    item_types: Res<ItemTypes>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let mut player_powered = false;
-
-    for item_power in player_query.iter() {
-        if item_power.diamond {
-            player_powered = true;
-        }
+    if !item_spawn_timer.timer.finished() {
+        return;
     }
 
-    let diamond_count = diamond_qeury.iter().count();
-
-    if !player_powered && diamond_count == 0 {
-        if diamond_spawn_timer.timer.finished() {
-            let window = window_query.get_single().unwrap();
-
-            let (spawn_area_width_start, spawn_area_width_end) =
-                (-window.width() / 2.0 + 20.0, window.width() / 2.0 - 20.0);
-            let (spawn_area_height_start, spawn_area_height_end) =
-                (-window.height() / 2.0 + 20.0, window.height() / 2.0 - 20.0);
-
-            let mut rng = rand::thread_rng();
-
-            let random_width = rng.gen_range(spawn_area_width_start..spawn_area_width_end);
-            let random_height = rng.gen_range(spawn_area_height_start..spawn_area_height_end);
-
-            let animation_indices = AnimationIndices { first: 0, last: 9 };
-
-            commands.spawn(PowerUpBundle {
-                power_up: PowerUp,
-                variant: ItemVariant::Diamond,
-                animation_indices: animation_indices,
-                animation_timer: AnimationTimer::default(),
-                movable: Movable::default(),
-                velocity: Velocity::default(),
-                sprite_sheet: SpriteSheetBundle {
-                    texture_atlas: game_textures.diamond.clone(),
-                    sprite: TextureAtlasSprite::new(animation_indices.first),
-                    transform: Transform {
-                        translation: Vec3::new(random_width, random_height, 1.0),
-                        scale: Vec3::splat(DIAMOND_SPRITE.scale),
-                        ..Default::default()
-                    },
-                    ..Default::default()
+    let rng = &mut game_rng.items;
+
+    let Some(item_key) = item_types.choose(rng, |key, item_type| {
+        let conditions = &item_type.conditions;
+        let on_field = power_up_query
+            .iter()
+            .filter(|item_variant| matches!(item_variant, ItemVariant::Item(item) if item == key))
+            .count();
+        let active = item_type.effect.is_some_and(|effect| {
+            player_query
+                .iter()
+                .any(|active_effects| active_effects.is_active(effect))
+        });
+
+        difficulty.elapsed.as_secs_f32() >= conditions.after
+            && on_field < conditions.max_on_field as usize
+            && !(conditions.unless_active && active)
+    }) else {
+        return;
+    };
+    let item_type = item_types.get(item_key);
+
+    let translation = random_spawn_position(&playfield, rng);
+
+    spawn_item(
+        &mut commands,
+        &sprite_registry,
+        item_key,
+        item_type,
+        translation,
+    );
+}
+
+pub fn item_spawn_timer_tick_system(
+    mut item_spawn_timer: ResMut<ItemSpawnTimer>,
+    fixed_time: Res<FixedTime>,
+) {
+    item_spawn_timer.timer.tick(fixed_time.period);
+}
+
+/// Roll the loot table of every enemy killed this tick, and drop what comes
+/// up where it died.
+pub fn enemy_loot_drop_system(
+    mut commands: Commands,
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+    enemy_types: Res<EnemyTypes>,
+    item_types: Res<ItemTypes>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = &mut game_rng.loot;
+
+    for enemy_killed in enemy_killed_events.iter() {
+        // a reload may have taken the enemy out of the manifest
+        let Some(enemy_type) = enemy_types.types.get(&enemy_killed.variant.0) else {
+            continue;
+        };
+
+        for loot_entry in enemy_type.loot.iter() {
+            if !rng.gen_bool(loot_entry.chance) {
+                continue;
+            }
+            // already warned about by `loot_table_check_system`
+            let Some(item_type) = item_types.types.get(&loot_entry.item) else {
+                continue;
+            };
+
+            // spread drops out a little so several don't stack up
+            let scatter = Vec2::new(
+                rng.gen_range(-LOOT_SCATTER..=LOOT_SCATTER),
+                rng.gen_range(-LOOT_SCATTER..=LOOT_SCATTER),
+            );
+            let translation = Vec3::new(
+                enemy_killed.position.x + scatter.x,
+                enemy_killed.position.y + scatter.y,
+                1.0,
+            );
+
+            let item = spawn_item(
+                &mut commands,
+                &sprite_registry,
+                &loot_entry.item,
+                item_type,
+                translation,
+            );
+            commands.entity(item).insert((
+                LootDrop {
+                    timer: Timer::from_seconds(LOOT_DROP_TIME, TimerMode::Once),
                 },
-            });
+                Movable { auto_despawn: true },
+            ));
         }
     }
 }
 
-pub fn diamond_spawn_timer_tick_system(
-    mut diamond_spawn_timer: ResMut<DiamondSpawnTimer>,
-    time: Res<Time>,
-) {
-    diamond_spawn_timer.timer.tick(time.delta());
+/// Warn about loot the item manifest doesn't have, which never drops, once
+/// either manifest is loaded.
+pub fn loot_table_check_system(enemy_types: Res<EnemyTypes>, item_types: Res<ItemTypes>) {
+    if !(enemy_types.is_changed() || item_types.is_changed()) {
+        return;
+    }
+
+    for (enemy_key, enemy_type) in enemy_types.types.iter() {
+        for loot_entry in enemy_type.loot.iter() {
+            if !item_types.types.contains_key(&loot_entry.item) {
+                warn!(
+                    "{}: enemy \"{}\" drops \"{}\", which isn't in {}",
+                    ENEMY_MANIFEST, enemy_key, loot_entry.item, ITEM_MANIFEST
+                );
+            }
+        }
+    }
 }
 
-pub fn power_up_animation_system(
-    time: Res<Time>,
-    mut power_up_query: Query<
-        (
-            &mut AnimationTimer,
-            &AnimationIndices,
-            &mut TextureAtlasSprite,
-        ),
-        With<PowerUp>,
-    >,
+/// Leave out items that can't be drawn with the loaded sprite sheets, once
+/// either manifest is loaded. Runs before any tick, so nothing tries to put
+/// them down.
+pub fn item_sprites_check_system(
+    mut item_types: ResMut<ItemTypes>,
+    sprite_registry: Res<SpriteRegistry>,
 ) {
-    for (mut power_up_animation_timer, power_up_animation_indices, mut power_up_sprite) in
-        power_up_query.iter_mut()
-    {
-        animate_sprite(
-            &mut power_up_sprite,
-            &power_up_animation_indices,
-            &mut power_up_animation_timer,
-            &time,
-        )
+    if !(item_types.is_changed() || sprite_registry.is_changed()) {
+        return;
+    }
+
+    let undrawable: Vec<(String, String)> = item_types
+        .types
+        .iter()
+        .filter_map(|(key, item_type)| {
+            let problem = sprite_registry
+                .check(&item_type.sprite, &[&item_type.animation])
+                .err()?;
+            Some((key.clone(), problem))
+        })
+        .collect();
+    for (key, problem) in undrawable.iter() {
+        warn!(
+            "{}: item \"{}\" {}, leaving it out",
+            ITEM_MANIFEST, key, problem
+        );
+        item_types.types.remove(key);
     }
 }
 
-pub fn diamond_power_timer_tick_system(
-    mut diamond_power_timer: ResMut<DiamondPowerTimer>,
-    time: Res<Time>,
+/// Scroll dropped loot along with the world, and clear it away once it
+/// leaves the playfield or has lain there too long.
+pub fn loot_drop_movement_system(
+    mut commands: Commands,
+    mut loot_query: Query<(Entity, &Movable, &mut LootDrop, &mut Transform)>,
+    playfield: Res<Playfield>,
+    fixed_time: Res<FixedTime>,
 ) {
-    diamond_power_timer.timer.tick(time.delta());
+    for (loot_entity, movable, mut loot_drop, mut loot_transform) in loot_query.iter_mut() {
+        let loot_translation = &mut loot_transform.translation;
+        loot_translation.x -= GROUND_SCROLL_STEP;
+
+        let off_screen = loot_translation.x < -playfield.width / 2.0 - 20.0;
+        if loot_drop.timer.tick(fixed_time.period).finished() || movable.auto_despawn && off_screen
+        {
+            commands.entity(loot_entity).despawn();
+        }
+    }
 }
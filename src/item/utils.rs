@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::animation::components::AnimationController;
+use crate::collision::components::Collider;
+use crate::common::components::{Interpolated, Movable, Velocity};
+use crate::common::resources::{Playfield, SpriteRegistry};
+use crate::item::assets::{Effect, EffectStacking, ItemType};
+use crate::item::bundles::PowerUpBundle;
+use crate::item::components::{ActiveEffects, ItemVariant, PowerUp};
+use crate::item::SHIELD_GRACE_TIME;
+
+/// A random spot for a power-up, clear of the edges of the playfield.
+pub fn random_spawn_position(playfield: &Playfield, rng: &mut impl Rng) -> Vec3 {
+    let (spawn_area_width_start, spawn_area_width_end) =
+        (-playfield.width / 2.0 + 20.0, playfield.width / 2.0 - 20.0);
+    let (spawn_area_height_start, spawn_area_height_end) = (
+        -playfield.height / 2.0 + 20.0,
+        playfield.height / 2.0 - 20.0,
+    );
+
+    let random_width = rng.gen_range(spawn_area_width_start..spawn_area_width_end);
+    let random_height = rng.gen_range(spawn_area_height_start..spawn_area_height_end);
+
+    Vec3::new(random_width, random_height, 1.0)
+}
+
+/// Put an `item_key` item down at `translation`, drawn and sized the way its
+/// `item_type` says.
+pub fn spawn_item(
+    commands: &mut Commands,
+    sprite_registry: &SpriteRegistry,
+    item_key: &str,
+    item_type: &ItemType,
+    translation: Vec3,
+) -> Entity {
+    let item_sprite = sprite_registry.get(&item_type.sprite);
+    let item_clip = item_sprite.clip(&item_type.animation);
+
+    commands
+        .spawn((
+            PowerUpBundle {
+                power_up: PowerUp,
+                variant: ItemVariant::Item(item_key.to_string()),
+                collider: item_type
+                    .pickup_radius
+                    .map(Collider::circle)
+                    .unwrap_or_else(|| item_sprite.collider()),
+                animation: AnimationController::new(&item_type.animation, item_clip.clone()),
+                movable: Movable::default(),
+                velocity: Velocity::default(),
+                sprite_sheet: SpriteSheetBundle {
+                    texture_atlas: item_sprite.atlas(),
+                    sprite: TextureAtlasSprite {
+                        index: item_clip.first,
+                        color: item_type.color,
+                        ..Default::default()
+                    },
+                    transform: Transform {
+                        translation,
+                        scale: Vec3::splat(item_type.scale.unwrap_or(item_sprite.sheet.scale)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            },
+            Interpolated::default(),
+        ))
+        .id()
+}
+
+/// Let a player's shield take a hit for them, leaving them invincible for a
+/// moment to get clear. Returns whether they had a shield to take it.
+pub fn shield_takes_hit(active_effects: &mut ActiveEffects) -> bool {
+    if !active_effects.remove(Effect::Shield) {
+        return false;
+    }
+    active_effects.grant(
+        Effect::Invincible,
+        SHIELD_GRACE_TIME,
+        EffectStacking::Refresh,
+    );
+    true
+}
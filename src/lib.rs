@@ -0,0 +1,18 @@
+pub mod animation;
+pub mod app;
+pub mod collision;
+pub mod common;
+pub mod difficulty;
+pub mod enemy;
+pub mod game;
+pub mod item;
+pub mod player;
+pub mod replay;
+pub mod score;
+pub mod ui;
+pub mod vfx;
+pub mod wave;
+pub mod weapon;
+pub mod world;
+
+pub use app::GameAppBuilder;
@@ -1,143 +1,41 @@
-pub mod common;
-pub mod enemy;
-pub mod game;
-pub mod item;
-pub mod player;
-pub mod score;
-pub mod ui;
-pub mod world;
-
-use bevy::prelude::*;
-use bevy::sprite::TextureAtlas;
-use bevy::window::{Window, WindowMode, WindowPlugin};
-use bevy::DefaultPlugins;
-
-use bevy_kira_audio::prelude::*;
-
-use common::resources::{GameAudio, GameTextures};
-use common::utils::{get_game_sound, get_texture_atlas};
-
-use item::DIAMOND_SPRITE;
-
-use enemy::EnemyPlugin;
-use enemy::{
-    ENEMY1_DEAD_SPRITE, ENEMY1_SPRITE, ENEMY2_DEAD_SPRITE, ENEMY2_SPRITE, ENEMY3_DEAD_SPRITE,
-    ENEMY3_SPRITE,
-};
-use game::GamePlugin;
-use player::{
-    PlayerPlugin, BULLET_SPRITE, PLAYER1_DEAD_SPRITE, PLAYER1_GHOST_SPRITE, PLAYER1_SPRITE,
-    PLAYER1_STATIC_SPRITE, PLAYER2_GHOST_SPRITE, PLAYER2_SPRITE, PLAYER2_STATIC_SPRITE,
-    PLAYER_DIAMOND_SPRITE,
-};
-
-use item::ItemPlugin;
-use score::ScorePlugin;
-use ui::UIPlugin;
-use world::WorldPlugin;
-
-pub fn setup_system(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-) {
-    // Game Textures
-    let player1_texture_atlas = get_texture_atlas(PLAYER1_SPRITE, &asset_server);
-    let player1_texture_handle = texture_atlases.add(player1_texture_atlas);
-
-    let player2_texture_atlas = get_texture_atlas(PLAYER2_SPRITE, &asset_server);
-    let player2_texture_handle = texture_atlases.add(player2_texture_atlas);
-
-    let player1_dead_texture_atlas = get_texture_atlas(PLAYER1_DEAD_SPRITE, &asset_server);
-    let player1_dead_texture_handle = texture_atlases.add(player1_dead_texture_atlas);
-
-    let player1_ghost_texture_atlas = get_texture_atlas(PLAYER1_GHOST_SPRITE, &asset_server);
-    let player1_ghost_texture_handle = texture_atlases.add(player1_ghost_texture_atlas);
-
-    let player2_ghost_texture_atlas = get_texture_atlas(PLAYER2_GHOST_SPRITE, &asset_server);
-    let player2_ghost_texture_handle = texture_atlases.add(player2_ghost_texture_atlas);
-
-    let player_diamond_texture_atlas = get_texture_atlas(PLAYER_DIAMOND_SPRITE, &asset_server);
-    let player_diamond_texture_handle = texture_atlases.add(player_diamond_texture_atlas);
-
-    let enemy_zombie_texture_atlas = get_texture_atlas(ENEMY1_SPRITE, &asset_server);
-    let enemy_zombie_texture_handle = texture_atlases.add(enemy_zombie_texture_atlas);
-
-    let enemy_zombie_dead_texture_atlas = get_texture_atlas(ENEMY1_DEAD_SPRITE, &asset_server);
-    let enemy_zombie_dead_texture_handle = texture_atlases.add(enemy_zombie_dead_texture_atlas);
-
-    let enemy_skeleton_texture_atlas = get_texture_atlas(ENEMY2_SPRITE, &asset_server);
-    let enemy_skeleton_texture_handle = texture_atlases.add(enemy_skeleton_texture_atlas);
-
-    let enemy_skeleton_dead_texture_atlas = get_texture_atlas(ENEMY2_DEAD_SPRITE, &asset_server);
-    let enemy_skeleton_dead_texture_handle = texture_atlases.add(enemy_skeleton_dead_texture_atlas);
-
-    let enemy_goblin_texture_atlas = get_texture_atlas(ENEMY3_SPRITE, &asset_server);
-    let enemy_goblin_texture_handle = texture_atlases.add(enemy_goblin_texture_atlas);
-
-    let enemy_goblin_dead_texture_atlas = get_texture_atlas(ENEMY3_DEAD_SPRITE, &asset_server);
-    let enemy_goblin_dead_texture_handle = texture_atlases.add(enemy_goblin_dead_texture_atlas);
-
-    let bullet_texture_atlas = get_texture_atlas(BULLET_SPRITE, &asset_server);
-    let bullet_texture_handle = texture_atlases.add(bullet_texture_atlas);
-
-    let diamond_texture_atlas = get_texture_atlas(DIAMOND_SPRITE, &asset_server);
-    let diamond_texture_handle = texture_atlases.add(diamond_texture_atlas);
-
-    let game_textures = GameTextures {
-        player_one: player1_texture_handle,
-        player_two: player2_texture_handle,
-        player_one_dead: player1_dead_texture_handle.clone(),
-        player_two_dead: player1_dead_texture_handle.clone(),
-        player_one_ghost: player1_ghost_texture_handle,
-        player_two_ghost: player2_ghost_texture_handle,
-        player_one_static: asset_server.load(PLAYER1_STATIC_SPRITE.file),
-        player_two_static: asset_server.load(PLAYER2_STATIC_SPRITE.file),
-        player_diamond: player_diamond_texture_handle,
-        enemy_zombie: enemy_zombie_texture_handle,
-        enemy_zombie_dead: enemy_zombie_dead_texture_handle,
-        enemy_skeleton: enemy_skeleton_texture_handle,
-        enemy_skeleton_dead: enemy_skeleton_dead_texture_handle,
-        enemy_goblin: enemy_goblin_texture_handle,
-        enemy_goblin_dead: enemy_goblin_dead_texture_handle,
-        bullet: bullet_texture_handle,
-        diamond: diamond_texture_handle,
-    };
-    commands.insert_resource(game_textures);
-
-    // Game Sounds
-    let player_dead_sound = get_game_sound("dead.ogg", &asset_server);
-    let player_shoot_sound = get_game_sound("shoot.ogg", &asset_server);
-    let enemy_dead_sound = get_game_sound("zombie-die.ogg", &asset_server);
-    let diamond_powerup_sound = get_game_sound("diamond-powerup.ogg", &asset_server);
-
-    let game_sounds = GameAudio {
-        player_dead: player_dead_sound,
-        player_shoot: player_shoot_sound,
-        enemy_dead: enemy_dead_sound,
-        diamond_powerup: diamond_powerup_sound,
-    };
-    commands.insert_resource(game_sounds);
-}
+use bevy_minimal::difficulty::resources::DifficultyPreset;
+use bevy_minimal::game::resources::GameMode;
+use bevy_minimal::GameAppBuilder;
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb_u8(85, 128, 0)))
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                mode: WindowMode::BorderlessFullscreen,
-                ..Default::default()
-            }),
-            ..Default::default()
-        }))
-        .add_plugin(GamePlugin)
-        .add_plugin(UIPlugin)
-        .add_plugin(AudioPlugin)
-        .add_plugin(WorldPlugin)
-        .add_plugin(ScorePlugin)
-        .add_plugin(PlayerPlugin)
-        .add_plugin(EnemyPlugin)
-        .add_plugin(ItemPlugin)
-        .add_startup_system(setup_system)
-        .run();
+    let mut builder = GameAppBuilder::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let seed = args
+                    .next()
+                    .and_then(|seed| seed.parse().ok())
+                    .expect("--seed expects an unsigned integer");
+                builder = builder.seed(seed);
+            }
+            "--solo" => builder = builder.game_mode(GameMode::Solo),
+            "--easy" => builder = builder.difficulty(DifficultyPreset::Easy),
+            "--hard" => builder = builder.difficulty(DifficultyPreset::Hard),
+            "--initials" => {
+                let initials = args.next().expect("--initials expects e.g. ABC or ABC,XYZ");
+                let (one, two) = initials.split_once(',').unwrap_or((&initials, "P2"));
+                builder = builder.initials(one, two);
+            }
+            "--record" => {
+                let path = args.next().expect("--record expects a file path");
+                builder = builder.record(path);
+            }
+            "--replay" => {
+                let path = args.next().expect("--replay expects a file path");
+                builder = builder
+                    .replay(&path)
+                    .unwrap_or_else(|error| panic!("Failed to load replay {}: {}", path, error));
+            }
+            _ => eprintln!("Unknown argument: {}", arg),
+        }
+    }
+
+    builder.build().run();
 }
@@ -8,6 +8,8 @@ pub enum ControlAction {
     Left,
     Right,
     AxisMove,
+    /// Which way to shoot, on the right stick.
+    AxisAim,
     Fire,
     Pause,
     Restart,
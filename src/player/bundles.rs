@@ -2,29 +2,25 @@ use bevy::prelude::*;
 
 use leafwing_input_manager::prelude::*;
 
-use crate::common::components::{
-    AnimationIndices, AnimationTimer, EntityLocation, Movable, Velocity, Vitality,
-};
+use crate::animation::components::AnimationController;
+use crate::collision::components::Collider;
+use crate::common::components::{Damage, Interpolated, Movable, Velocity, Vitality};
+use crate::common::resources::SpriteRegistry;
 use crate::player::actions::ControlAction;
 use crate::player::components::{
-    Lives, Player, PlayerDead, PlayerDeadLocation, PlayerVariant, Score,
+    Fireball, Lives, Player, PlayerDead, PlayerInput, PlayerVariant, Score,
 };
 
-use crate::item::components::ItemPower;
-
-#[derive(Bundle)]
-pub struct PlayerDeadLocationBundle {
-    pub entity: PlayerDeadLocation,
-    pub location: EntityLocation,
-}
+use crate::item::components::ActiveEffects;
+use crate::weapon::components::Weapon;
 
 #[derive(Bundle)]
 pub struct PlayerDeadBundle {
     pub entity: PlayerDead,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
+    pub animation: AnimationController,
     pub movable: Movable,
     pub velocity: Velocity,
+    pub interpolated: Interpolated,
 
     #[bundle]
     pub sprite_sheet: SpriteSheetBundle,
@@ -36,10 +32,13 @@ pub struct PlayerBundle {
     pub variant: PlayerVariant,
     pub lives: Lives,
     pub vitality: Vitality,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
-    pub item_power: ItemPower,
+    pub collider: Collider,
+    pub animation: AnimationController,
+    pub active_effects: ActiveEffects,
+    pub weapon: Weapon,
     pub score: Score,
+    pub input: PlayerInput,
+    pub interpolated: Interpolated,
 
     #[bundle]
     pub input_manager: InputManagerBundle<ControlAction>,
@@ -48,7 +47,61 @@ pub struct PlayerBundle {
     pub sprite_sheet: SpriteSheetBundle,
 }
 
+#[derive(Bundle)]
+pub struct FireballBundle {
+    pub entity: Fireball,
+    pub variant: PlayerVariant,
+    pub damage: Damage,
+    pub collider: Collider,
+    pub movable: Movable,
+    pub velocity: Velocity,
+    pub interpolated: Interpolated,
+
+    #[bundle]
+    pub sprite_sheet: SpriteSheetBundle,
+}
+
 impl PlayerBundle {
+    pub fn animation(
+        sprite_registry: &SpriteRegistry,
+        player_variant: PlayerVariant,
+    ) -> AnimationController {
+        let powered = sprite_registry.get("player_diamond").clip("run");
+        let hurt = sprite_registry.get("player_dead").clip("hurt").once();
+
+        match player_variant {
+            PlayerVariant::One => AnimationController::new(
+                "idle",
+                sprite_registry.get("player_one_idle").clip("idle"),
+            )
+            .with_clip("run", sprite_registry.get("player_one").clip("run"))
+            .with_clip(
+                "shoot",
+                sprite_registry.get("player_one_shoot").clip("shoot").once(),
+            )
+            .with_clip(
+                "ghost",
+                sprite_registry.get("player_one_ghost").clip("float"),
+            )
+            .with_clip("powered", powered)
+            .with_clip("hurt", hurt),
+            PlayerVariant::Two => {
+                AnimationController::new("idle", sprite_registry.get("player_two").clip("idle"))
+                    .with_clip("run", sprite_registry.get("player_two").clip("run"))
+                    .with_clip(
+                        "shoot",
+                        sprite_registry.get("player_two").clip("shoot").once(),
+                    )
+                    .with_clip(
+                        "ghost",
+                        sprite_registry.get("player_two_ghost").clip("float"),
+                    )
+                    .with_clip("powered", powered)
+                    .with_clip("hurt", hurt)
+            }
+        }
+    }
+
     pub fn input_map(player_variant: PlayerVariant) -> InputMap<ControlAction> {
         let mut input_map = match player_variant {
             PlayerVariant::One => InputMap::new([
@@ -57,6 +110,7 @@ impl PlayerBundle {
                 (KeyCode::A, ControlAction::Left),
                 (KeyCode::D, ControlAction::Right),
                 (KeyCode::B, ControlAction::Fire),
+                (KeyCode::R, ControlAction::Restart),
             ])
             .set_gamepad(Gamepad { id: 0 })
             .build(),
@@ -66,12 +120,14 @@ impl PlayerBundle {
                 (KeyCode::Left, ControlAction::Left),
                 (KeyCode::Right, ControlAction::Right),
                 (KeyCode::J, ControlAction::Fire),
+                (KeyCode::R, ControlAction::Restart),
             ])
             .set_gamepad(Gamepad { id: 1 })
             .build(),
         };
 
         input_map.insert(DualAxis::left_stick(), ControlAction::AxisMove);
+        input_map.insert(DualAxis::right_stick(), ControlAction::AxisAim);
 
         input_map.insert_multiple([
             (GamepadButtonType::DPadUp, ControlAction::Up),
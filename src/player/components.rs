@@ -5,7 +5,7 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct Player;
 
-#[derive(Component, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub enum PlayerVariant {
     One,
     Two,
@@ -31,15 +31,25 @@ impl Default for Lives {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 pub struct Score {
     pub value: u32,
 }
 
-impl Default for Score {
-    fn default() -> Self {
-        Self { value: 0 }
-    }
+/// What a player asked for during one simulation tick, gathered from
+/// their `ActionState` every frame and consumed by the fixed update.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq)]
+pub struct PlayerInput {
+    pub movement: Vec2,
+    /// Unit direction to shoot in: the aim stick, or else the way the player
+    /// last moved. Zero until either is used, which shoots ahead.
+    pub aim: Vec2,
+    pub fire: bool,
+    /// Fire was pressed since the last tick. Latched so that a press is
+    /// not lost on frames where no tick runs.
+    pub fire_pressed: bool,
+    /// Restart was pressed since the last tick.
+    pub restart_pressed: bool,
 }
 
 #[derive(Component)]
@@ -47,6 +57,3 @@ pub struct Fireball;
 
 #[derive(Component)]
 pub struct PlayerDead;
-
-#[derive(Component)]
-pub struct PlayerDeadLocation;
@@ -1,50 +1,52 @@
-use std::collections::HashSet;
-
 use bevy::prelude::*;
-use bevy::sprite::collide_aabb::collide;
-use bevy::window::PrimaryWindow;
-
-use bevy_kira_audio::prelude::Audio;
-use bevy_kira_audio::{AudioControl, AudioTween};
 
 use leafwing_input_manager::prelude::ActionState;
 use leafwing_input_manager::InputManagerBundle;
 
-use crate::common::components::{AnimationIndices, AnimationTimer, EntityLocation, Vitality};
-use crate::common::resources::{GameAudio, GameTextures};
-use crate::common::utils::{animate_sprite, animate_sprite_single};
+use crate::animation::components::AnimationController;
+use crate::collision::events::{FireballHitEnemy, PlayerHitPowerUp};
+use crate::common::components::{Damage, Health, Interpolated, Vitality};
+use crate::common::resources::{Playfield, SpriteRegistry};
 use crate::common::{SCROLL_X_VELOCITY, SCROLL_Y_VELOCITY};
+use crate::game::events::{
+    BossDefeated, EnemyDamaged, EnemyKilled, KillCause, PlayerDied, PlayerHurt, PowerUpCollected,
+    ShotFired,
+};
+use crate::game::resources::GameMode;
 
 use crate::player::actions::ControlAction;
 use crate::player::bundles::{PlayerBundle, PlayerDeadBundle};
 use crate::player::components::{
-    Fireball, Lives, Player, PlayerDead, PlayerDeadLocation, PlayerVariant, Score,
+    Fireball, Lives, Player, PlayerDead, PlayerInput, PlayerVariant, Score,
 };
-use crate::player::{BULLET_SPRITE, PLAYER1_SPRITE, PLAYER2_SPRITE, PLAYER_SPEED};
+use crate::player::utils::spawn_shot;
+use crate::player::PLAYER_SPEED;
 
-use crate::item::components::{ItemPower, PowerUp};
-use crate::item::resources::DiamondPowerTimer;
-use crate::item::DIAMOND_SPRITE;
+use crate::item::assets::Effect;
+use crate::item::components::{ActiveEffects, ItemVariant, PowerUp};
+use crate::item::resources::ItemTypes;
+use crate::item::{INVINCIBLE_BLINK_TIME, SPEED_BOOST_MULTIPLIER};
 
-use crate::enemy::bundles::EnemyDeadLocationBundle;
-use crate::enemy::components::{Enemy, EnemyDeadLocation, EnemyVariant};
-use crate::enemy::ENEMY1_SPRITE;
+use crate::enemy::boss::components::Boss;
+use crate::enemy::components::{Elite, Enemy, EnemyVariant, Hurt};
 
 use crate::common::components::{Movable, Velocity};
 use crate::common::{BASE_SPEED, TIME_STEP};
 
+use crate::weapon::components::{Bouncing, Piercing, Weapon, WeaponPickup};
+use crate::weapon::resources::WeaponTypes;
+use crate::weapon::{CHARGED_COLOR, STARTING_WEAPON};
+
 pub fn player_spawn_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    sprite_registry: Res<SpriteRegistry>,
+    playfield: Res<Playfield>,
+    game_mode: Res<GameMode>,
+    weapon_types: Res<WeaponTypes>,
 ) {
-    let window = window_query.get_single().unwrap();
-
-    // Use only the subset of sprites in the sheet that make up the run animation
-    let animation_indices = AnimationIndices {
-        first: 10,
-        last: 13,
-    };
+    let starting_weapon = weapon_types.get(STARTING_WEAPON);
+    let player_one_sprite = sprite_registry.get("player_one");
+    let player_two_sprite = sprite_registry.get("player_two");
 
     // Player 1
     commands.spawn(PlayerBundle {
@@ -52,46 +54,56 @@ pub fn player_spawn_system(
         variant: PlayerVariant::One,
         lives: Lives::default(),
         vitality: Vitality::Alive,
-        animation_indices: animation_indices,
-        animation_timer: AnimationTimer::default(),
-        item_power: ItemPower::default(),
+        collider: player_one_sprite.collider(),
+        animation: PlayerBundle::animation(&sprite_registry, PlayerVariant::One),
+        active_effects: ActiveEffects::default(),
+        weapon: Weapon::new(STARTING_WEAPON, starting_weapon),
         score: Score::default(),
+        input: PlayerInput::default(),
+        interpolated: Interpolated::default(),
         input_manager: InputManagerBundle {
             input_map: PlayerBundle::input_map(PlayerVariant::One),
             ..Default::default()
         },
         sprite_sheet: SpriteSheetBundle {
-            texture_atlas: game_textures.player_one.clone(),
-            sprite: TextureAtlasSprite::new(11),
+            texture_atlas: player_one_sprite.atlas(),
+            sprite: TextureAtlasSprite::new(player_one_sprite.animation("idle").first),
             transform: Transform {
-                translation: Vec3::new(-window.width() / 4.0, 0.0, 10.0),
-                scale: Vec3::splat(PLAYER1_SPRITE.scale),
+                translation: Vec3::new(-playfield.width / 4.0, 0.0, 10.0),
+                scale: Vec3::splat(player_one_sprite.sheet.scale),
                 ..Default::default()
             },
             ..Default::default()
         },
     });
 
+    if *game_mode == GameMode::Solo {
+        return;
+    }
+
     // Player 2
     commands.spawn(PlayerBundle {
         entity: Player,
         variant: PlayerVariant::Two,
         lives: Lives::default(),
         vitality: Vitality::Alive,
-        animation_indices: animation_indices,
-        animation_timer: AnimationTimer::default(),
-        item_power: ItemPower::default(),
+        collider: player_two_sprite.collider(),
+        animation: PlayerBundle::animation(&sprite_registry, PlayerVariant::Two),
+        active_effects: ActiveEffects::default(),
+        weapon: Weapon::new(STARTING_WEAPON, starting_weapon),
         score: Score::default(),
+        input: PlayerInput::default(),
+        interpolated: Interpolated::default(),
         input_manager: InputManagerBundle {
             input_map: PlayerBundle::input_map(PlayerVariant::Two),
             ..Default::default()
         },
         sprite_sheet: SpriteSheetBundle {
-            texture_atlas: game_textures.player_two.clone(),
-            sprite: TextureAtlasSprite::new(11),
+            texture_atlas: player_two_sprite.atlas(),
+            sprite: TextureAtlasSprite::new(player_two_sprite.animation("idle").first),
             transform: Transform {
-                translation: Vec3::new(-window.width() / 4.0 + 50.0, 10.0, 10.0),
-                scale: Vec3::splat(PLAYER2_SPRITE.scale),
+                translation: Vec3::new(-playfield.width / 4.0 + 50.0, 10.0, 10.0),
+                scale: Vec3::splat(player_two_sprite.sheet.scale),
                 ..Default::default()
             },
             ..Default::default()
@@ -99,129 +111,134 @@ pub fn player_spawn_system(
     });
 }
 
+/// Bring a dead player back when they press restart, with the weapon they
+/// started out with.
 pub fn player_respawn_system(
-    mut player_query: Query<
-        (
-            &PlayerVariant,
-            &mut Vitality,
-            &Lives,
-            &ActionState<ControlAction>,
-            &mut Handle<TextureAtlas>,
-        ),
-        With<Player>,
-    >,
-    keyboard_input: Res<Input<KeyCode>>,
-    game_textures: Res<GameTextures>,
+    mut player_query: Query<(&mut Vitality, &Lives, &PlayerInput, &mut Weapon), With<Player>>,
+    weapon_types: Res<WeaponTypes>,
 ) {
-    for (player, mut player_state, player_lives, controller_input, mut sprite_handle) in
-        player_query.iter_mut()
-    {
-        if *player_state == Vitality::Dead && player_lives.count > 0 {
-            if keyboard_input.just_pressed(KeyCode::R)
-                || controller_input.just_pressed(ControlAction::Restart)
-            {
-                let player_sprite_atlas = match player {
-                    PlayerVariant::One => game_textures.player_one.clone(),
-                    PlayerVariant::Two => game_textures.player_two.clone(),
-                };
-                *player_state = Vitality::Alive;
-                *sprite_handle = player_sprite_atlas;
-            }
+    for (mut player_state, player_lives, player_input, mut weapon) in player_query.iter_mut() {
+        if *player_state == Vitality::Dead && player_lives.count > 0 && player_input.restart_pressed
+        {
+            *player_state = Vitality::Alive;
+            *weapon = Weapon::new(STARTING_WEAPON, weapon_types.get(STARTING_WEAPON));
         }
     }
 }
 
+/// Fire each player's weapon where they are aiming as soon as fire is
+/// pressed, then on at the weapon's fire rate while it is held. A weapon
+/// with a charge shot also charges up while fire is held, and lets the
+/// charged shot off once it comes back up.
+#[allow(clippy::type_complexity)]
 pub fn player_fire_system(
     mut commands: Commands,
-    player_query: Query<
+    mut player_query: Query<
         (
             &Transform,
-            &ActionState<ControlAction>,
+            &PlayerInput,
             &Vitality,
             &PlayerVariant,
+            &mut Weapon,
+            &mut TextureAtlasSprite,
         ),
         With<Player>,
     >,
-    game_textures: Res<GameTextures>,
-    game_audio: Res<GameAudio>,
-    audio: Res<Audio>,
+    sprite_registry: Res<SpriteRegistry>,
+    weapon_types: Res<WeaponTypes>,
+    fixed_time: Res<FixedTime>,
+    mut shot_events: EventWriter<ShotFired>,
 ) {
-    for (player_transform, player_fire_action, player_state, player_variant) in player_query.iter()
+    let player_sheet = &sprite_registry.get("player_one").sheet;
+    let x_offset = player_sheet.width / 2.0 * player_sheet.scale + 10.0;
+
+    for (
+        player_transform,
+        player_input,
+        player_state,
+        player_variant,
+        mut weapon,
+        mut player_sprite,
+    ) in player_query.iter_mut()
     {
-        if *player_state == Vitality::Alive && player_fire_action.just_pressed(ControlAction::Fire)
-        {
-            let (player_x, player_y) = (
-                player_transform.translation.x,
-                player_transform.translation.y,
-            );
-            let x_offset = PLAYER1_SPRITE.width / 2.0 * PLAYER1_SPRITE.scale + 10.0;
-
-            // println!("{:#?}", player_variant);
-
-            commands.spawn((
-                SpriteSheetBundle {
-                    texture_atlas: game_textures.bullet.clone(),
-                    sprite: TextureAtlasSprite::new(15),
-                    transform: Transform {
-                        translation: Vec3::new(player_x + x_offset, player_y, 1.0),
-                        scale: Vec3::splat(3.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                Fireball {},
-                Movable { auto_despawn: true },
-                Velocity { x: 1.0, y: 0.0 },
-                player_variant.clone(),
-            ));
-            audio.play(game_audio.player_shoot.clone());
+        let weapon = &mut *weapon;
+        // a reload may have taken the weapon out of the manifest
+        if !weapon_types.types.contains_key(&weapon.kind) {
+            *weapon = Weapon::new(STARTING_WEAPON, weapon_types.get(STARTING_WEAPON));
+        }
+        let weapon_type = weapon_types.get(&weapon.kind);
+        weapon.cooldown.tick(fixed_time.period);
+
+        let mut charged = false;
+        if let Some(charge) = &mut weapon.charge {
+            if *player_state != Vitality::Alive {
+                charge.reset();
+            } else if player_input.fire && !player_input.fire_pressed {
+                charge.tick(fixed_time.period);
+            } else if !player_input.fire && !charge.elapsed().is_zero() {
+                charged = charge.finished();
+                charge.reset();
+            }
+        }
+
+        player_sprite.color = match &weapon.charge {
+            Some(charge) if charge.finished() => CHARGED_COLOR,
+            _ => Color::WHITE,
+        };
+
+        if *player_state != Vitality::Alive {
+            continue;
         }
+
+        let fire = player_input.fire_pressed || player_input.fire;
+        if !(charged || weapon.cooldown.finished() && fire) {
+            continue;
+        }
+        weapon.cooldown.reset();
+
+        let aim = match player_input.aim {
+            Vec2::ZERO => Vec2::X,
+            aim => aim,
+        };
+        let fireball_translation =
+            (player_transform.translation.truncate() + aim * x_offset).extend(1.0);
+
+        spawn_shot(
+            &mut commands,
+            &sprite_registry,
+            weapon_type,
+            weapon_type.charge.as_ref().filter(|_| charged),
+            player_variant,
+            fireball_translation,
+            aim,
+        );
+
+        shot_events.send(ShotFired {
+            player: player_variant.clone(),
+            position: fireball_translation,
+        });
     }
 }
 
-pub fn player_movement_system(
-    mut player_query: Query<
-        (
-            &mut Transform,
-            &ActionState<ControlAction>,
-            &AnimationIndices,
-            &mut AnimationTimer,
-            &mut TextureAtlasSprite,
-            &mut Handle<TextureAtlas>,
-            &ItemPower,
-        ),
-        With<Player>,
-    >,
-
-    time: Res<Time>,
-    game_textures: Res<GameTextures>,
+/// Gather each player's controls for the next simulation tick.
+pub fn player_input_system(
+    mut player_query: Query<(&ActionState<ControlAction>, &mut PlayerInput), With<Player>>,
 ) {
-    for (
-        mut player_transform,
-        player_move_action,
-        animation_indices,
-        mut animation_timer,
-        mut sprite,
-        mut sprite_handle,
-        item_power,
-    ) in player_query.iter_mut()
-    {
-        let mut direction = Vec3::ZERO;
+    for (player_action, mut player_input) in player_query.iter_mut() {
+        let mut direction = Vec2::ZERO;
         for input_direction in ControlAction::PLAYER_MOVE {
-            if player_move_action.pressed(input_direction) {
+            if player_action.pressed(input_direction) {
                 match input_direction {
-                    ControlAction::Up => direction += Vec3::new(0.0, 1.0, 0.0),
-                    ControlAction::Down => direction += Vec3::new(0.0, -1.0, 0.0),
-                    ControlAction::Left => direction += Vec3::new(-1.0, 0.0, 0.0),
-                    ControlAction::Right => direction += Vec3::new(1.0, 0.0, 0.0),
+                    ControlAction::Up => direction += Vec2::new(0.0, 1.0),
+                    ControlAction::Down => direction += Vec2::new(0.0, -1.0),
+                    ControlAction::Left => direction += Vec2::new(-1.0, 0.0),
+                    ControlAction::Right => direction += Vec2::new(1.0, 0.0),
                     ControlAction::AxisMove => {
                         // Each action has a button-like state of its own that you can check
                         // We're working with gamepads, so we want to defensively ensure that we're using the clamped values
 
-                        if let Some(axis_move) =
-                            player_move_action.axis_pair(ControlAction::AxisMove)
-                        {
-                            direction += Vec3::new(axis_move.x(), axis_move.y(), 0.0);
+                        if let Some(axis_move) = player_action.axis_pair(ControlAction::AxisMove) {
+                            direction += Vec2::new(axis_move.x(), axis_move.y());
                         }
                     }
 
@@ -230,57 +247,133 @@ pub fn player_movement_system(
             }
         }
 
-        if item_power.diamond {
-            *sprite_handle = game_textures.player_diamond.clone();
+        // keep aiming the same way once the stick or movement lets go
+        let aim = player_action
+            .axis_pair(ControlAction::AxisAim)
+            .filter(|_| player_action.pressed(ControlAction::AxisAim))
+            .map(|axis_aim| Vec2::new(axis_aim.x(), axis_aim.y()))
+            .unwrap_or(direction);
+        if let Some(aim) = aim.try_normalize() {
+            player_input.aim = aim;
         }
 
-        if direction.length() > 0.0 {
-            animate_sprite(&mut sprite, &animation_indices, &mut animation_timer, &time)
-        } else {
-            if item_power.diamond {
-                animate_sprite(&mut sprite, &animation_indices, &mut animation_timer, &time)
-            } else {
-                sprite.index = 11;
-            }
-        }
+        player_input.movement = direction;
+        player_input.fire = player_action.pressed(ControlAction::Fire);
+        player_input.fire_pressed |= player_action.just_pressed(ControlAction::Fire);
+        player_input.restart_pressed |= player_action.just_pressed(ControlAction::Restart);
+    }
+}
 
-        player_transform.translation += direction * PLAYER_SPEED * time.delta_seconds();
+/// Forget presses that the tick has just handled.
+pub fn player_input_consume_system(mut player_query: Query<&mut PlayerInput, With<Player>>) {
+    for mut player_input in player_query.iter_mut() {
+        player_input.fire_pressed = false;
+        player_input.restart_pressed = false;
     }
 }
 
-pub fn player_diamond_power_system(
+pub fn player_movement_system(
+    mut player_query: Query<(&mut Transform, &PlayerInput, &ActiveEffects), With<Player>>,
+) {
+    for (mut player_transform, player_input, active_effects) in player_query.iter_mut() {
+        let direction = player_input.movement.extend(0.0);
+        let speed = if active_effects.is_active(Effect::Speed) {
+            PLAYER_SPEED * SPEED_BOOST_MULTIPLIER
+        } else {
+            PLAYER_SPEED
+        };
+        player_transform.translation += direction * speed * TIME_STEP;
+    }
+}
+
+/// Pick each player's clip from what they are doing this tick.
+pub fn player_animation_system(
+    mut shot_events: EventReader<ShotFired>,
+    mut player_hurt_events: EventReader<PlayerHurt>,
     mut player_query: Query<
-        (&PlayerVariant, &mut Handle<TextureAtlas>, &mut ItemPower),
+        (
+            &PlayerVariant,
+            &Vitality,
+            &PlayerInput,
+            &ActiveEffects,
+            &mut AnimationController,
+        ),
         With<Player>,
     >,
-    game_textures: Res<GameTextures>,
-    diamond_power_timer: Res<DiamondPowerTimer>,
 ) {
-    for (player_variant, mut sprite_handle, mut item_power) in player_query.iter_mut() {
-        let player_texture = match player_variant {
-            PlayerVariant::One => game_textures.player_one.clone(),
-            PlayerVariant::Two => game_textures.player_two.clone(),
+    let shooters: Vec<PlayerVariant> = shot_events.iter().map(|shot| shot.player.clone()).collect();
+    let hurt: Vec<PlayerVariant> = player_hurt_events
+        .iter()
+        .map(|player_hurt| player_hurt.player.clone())
+        .collect();
+
+    for (player_variant, player_vitality, player_input, active_effects, mut animation) in
+        player_query.iter_mut()
+    {
+        if *player_vitality == Vitality::Dead {
+            animation.play("ghost");
+        } else if active_effects.is_active(Effect::Diamond) {
+            animation.play("powered");
+        } else if hurt.contains(player_variant) {
+            animation.restart("hurt");
+        } else if shooters.contains(player_variant) {
+            animation.restart("shoot");
+        } else if ["hurt", "shoot"].contains(&animation.current()) && !animation.finished() {
+            // let the stagger or the shot play out
+        } else if player_input.movement != Vec2::ZERO {
+            animation.play("run");
+        } else {
+            animation.play("idle");
+        }
+    }
+}
+
+/// Run each player's effects down, and let them go once they wear off.
+pub fn player_active_effects_system(
+    mut player_query: Query<(&PlayerVariant, &mut ActiveEffects), With<Player>>,
+    fixed_time: Res<FixedTime>,
+) {
+    for (player_variant, mut active_effects) in player_query.iter_mut() {
+        for effect in active_effects.tick(fixed_time.period) {
+            info!("Player {} lost {:?} power", player_variant, effect);
+        }
+    }
+}
+
+/// Blink players while they are invincible.
+pub fn player_invincible_blink_system(
+    mut player_query: Query<(&ActiveEffects, &mut Visibility), With<Player>>,
+) {
+    for (active_effects, mut visibility) in player_query.iter_mut() {
+        let shown = match active_effects.timer(Effect::Invincible) {
+            Some(timer) => ((timer.elapsed_secs() / INVINCIBLE_BLINK_TIME) as u32) & 1 == 0,
+            None => true,
         };
-        if diamond_power_timer.timer.just_finished() && item_power.diamond {
-            item_power.diamond = false;
-            *sprite_handle = player_texture;
-            break;
+        let blink_visibility = if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        if *visibility != blink_visibility {
+            *visibility = blink_visibility;
         }
     }
 }
 
 pub fn player_confinement_system(
     mut player_query: Query<&mut Transform, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
 ) {
+    let player_sheet = &sprite_registry.get("player_one").sheet;
+
     // if let Ok(mut player_transform) = player_query.get_single_mut() {
     for mut player_transform in player_query.iter_mut() {
-        let window = window_query.get_single().unwrap();
-
-        let left_window_edge = -window.width() / 2.0 + PLAYER1_SPRITE.width / 2.0;
-        let right_window_edge = window.width() / 2.0 - PLAYER1_SPRITE.width / 2.0;
-        let bottom_window_edge = -window.height() / 2.0 + PLAYER1_SPRITE.height;
-        let top_window_edge = window.height() / 2.0 - PLAYER1_SPRITE.height;
+        let left_window_edge = -playfield.width / 2.0 + player_sheet.width / 2.0;
+        let right_window_edge = playfield.width / 2.0 - player_sheet.width / 2.0;
+        let bottom_window_edge = -playfield.height / 2.0 + player_sheet.height;
+        let top_window_edge = playfield.height / 2.0 - player_sheet.height;
 
         // let mut translation = player_transform.translation;
         let mut player_x = player_transform.translation.x;
@@ -304,186 +397,222 @@ pub fn player_confinement_system(
     }
 }
 
+/// Move fireballs along their velocity, turned to face the way they are
+/// going, and despawn them once they leave the playfield.
+#[allow(clippy::type_complexity)]
 pub fn fireball_movement_system(
     mut commands: Commands,
-    mut entity_query: Query<(Entity, &Velocity, &mut Transform, &Movable), With<Fireball>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut entity_query: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &mut Transform,
+            &Movable,
+            Option<&mut Bouncing>,
+        ),
+        With<Fireball>,
+    >,
+    playfield: Res<Playfield>,
 ) {
-    let window = window_query.get_single().unwrap();
-    for (entity, velocity, mut transform, movable) in entity_query.iter_mut() {
+    let (x_margin, y_margin) = (playfield.width / 2.0, playfield.height / 2.0);
+
+    for (entity, mut velocity, mut transform, movable, bouncing) in entity_query.iter_mut() {
         let translation = &mut transform.translation;
-        translation.x += velocity.x * TIME_STEP * BASE_SPEED + 0.5;
+        translation.x += velocity.x * TIME_STEP * BASE_SPEED;
+        translation.y += velocity.y * TIME_STEP * BASE_SPEED;
+
+        // glance off the edges while it has bounces left
+        let mut held_in = false;
+        if let Some(mut bouncing) = bouncing {
+            held_in = bouncing.remaining > 0;
+            if held_in && translation.y.abs() > y_margin && translation.y * velocity.y > 0.0 {
+                velocity.y = -velocity.y;
+                bouncing.remaining -= 1;
+            } else if held_in && translation.x.abs() > x_margin && translation.x * velocity.x > 0.0
+            {
+                velocity.x = -velocity.x;
+                bouncing.remaining -= 1;
+            }
+        }
 
-        if movable.auto_despawn {
+        transform.rotation = Quat::from_rotation_z(velocity.y.atan2(velocity.x));
+
+        if movable.auto_despawn && !held_in {
             // despawn when out of screen
-            let window_margin = window.width() / 2.0;
-            if translation.x > window_margin {
+            let translation = transform.translation;
+            if translation.x.abs() > x_margin || translation.y.abs() > y_margin {
                 commands.entity(entity).despawn();
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn player_fireball_hit_enemy_system(
     mut commands: Commands,
-    fireball_query: Query<(Entity, &Transform, &PlayerVariant), With<Fireball>>,
-    mut player_query: Query<(&PlayerVariant, &mut Score), With<Player>>,
-    mut enemy_query: Query<(Entity, &EnemyVariant, &Vitality, &Transform), With<Enemy>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    game_audio: Res<GameAudio>,
-    audio: Res<Audio>,
+    mut collision_events: EventReader<FireballHitEnemy>,
+    mut fireball_query: Query<
+        (&PlayerVariant, &Damage, &Velocity, Option<&mut Piercing>),
+        With<Fireball>,
+    >,
+    mut enemy_query: Query<
+        (
+            &EnemyVariant,
+            &mut Vitality,
+            &mut Health,
+            &Transform,
+            Option<&Boss>,
+            Option<&Elite>,
+        ),
+        With<Enemy>,
+    >,
+    playfield: Res<Playfield>,
+    mut enemy_damaged_events: EventWriter<EnemyDamaged>,
+    mut enemy_killed_events: EventWriter<EnemyKilled>,
+    mut boss_defeated_events: EventWriter<BossDefeated>,
 ) {
-    let window = window_query.get_single().unwrap();
-    let world_right_edge = window.width() / 2.0 - 20.0;
+    let world_right_edge = playfield.width / 2.0 - 20.0;
 
-    let mut despawned_entities: HashSet<Entity> = HashSet::new();
+    // A fireball's hits arrive one after another, and it only hits the first
+    // enemy it can, or as many as it can pierce.
+    let mut spent_fireball = None;
 
-    // iterate through the lasers
-    for (fireball_entity, fireball_transform, fireball_player_variant) in fireball_query.iter() {
-        // iterate through the enemies
-        for (enemy_entity, enemy_variant, enemy_vitality, enemy_transform) in enemy_query.iter_mut()
-        {
-            if despawned_entities.contains(&enemy_entity)
-                || despawned_entities.contains(&fireball_entity)
-                || enemy_vitality == &Vitality::Dead
-            {
+    for collision in collision_events.iter() {
+        if spent_fireball == Some(collision.fireball) {
+            continue;
+        }
+        let Ok((fireball_player_variant, fireball_damage, fireball_velocity, mut piercing)) =
+            fireball_query.get_mut(collision.fireball)
+        else {
+            continue;
+        };
+        // each enemy is only hit once on the way through
+        if let Some(piercing) = &piercing {
+            if piercing.hits.contains(&collision.enemy) {
                 continue;
             }
+        }
+        let Ok((enemy_variant, mut enemy_vitality, mut enemy_health, enemy_transform, boss, elite)) =
+            enemy_query.get_mut(collision.enemy)
+        else {
+            continue;
+        };
 
-            // determine if collision
-            let collision = collide(
-                fireball_transform.translation,
-                Vec2::new(
-                    BULLET_SPRITE.width * BULLET_SPRITE.scale,
-                    BULLET_SPRITE.height * BULLET_SPRITE.scale,
-                ),
-                enemy_transform.translation,
-                Vec2::new(ENEMY1_SPRITE.width, ENEMY1_SPRITE.height),
-            );
-
-            // if enemy has entered the screen
-            if enemy_transform.translation.x < world_right_edge {
-                // perform collision
-
-                if collision.is_some() {
-                    // remove the enemy
-
-                    // println!("player_fireball_variant: {:#?}", fireball_player_variant);
-
-                    for (player_variant, mut player_score) in player_query.iter_mut() {
-                        if player_variant == fireball_player_variant {
-                            player_score.value += 1;
-                        }
-                    }
+        // only once the enemy has entered the screen
+        if *enemy_vitality == Vitality::Dead || enemy_transform.translation.x >= world_right_edge {
+            continue;
+        }
 
-                    audio.play(game_audio.enemy_dead.clone());
+        match &mut piercing {
+            Some(piercing) if piercing.remaining > 0 => {
+                piercing.hits.push(collision.enemy);
+                piercing.remaining -= 1;
+            }
+            _ => {
+                // remove the fireball
+                commands.entity(collision.fireball).despawn();
+                spent_fireball = Some(collision.fireball);
+            }
+        }
 
-                    commands.entity(enemy_entity).despawn();
-                    despawned_entities.insert(enemy_entity);
+        let health_before = enemy_health.current;
+        let killed = enemy_health.damage(fireball_damage.amount);
 
-                    // remove the laser
-                    commands.entity(fireball_entity).despawn();
-                    despawned_entities.insert(fireball_entity);
+        enemy_damaged_events.send(EnemyDamaged {
+            variant: enemy_variant.clone(),
+            attacker: fireball_player_variant.clone(),
+            amount: health_before - enemy_health.current,
+            position: enemy_transform.translation,
+        });
 
-                    commands.spawn(EnemyDeadLocationBundle {
-                        entity: EnemyDeadLocation,
-                        variant: enemy_variant.clone(),
-                        location: EntityLocation {
-                            x: enemy_transform.translation.x,
-                            y: enemy_transform.translation.y,
-                            z: 0.0,
-                        },
-                    });
+        if !killed {
+            // the boss stands its ground
+            let knockback = match boss {
+                Some(_) => 0.0,
+                None => fireball_damage.knockback,
+            };
+            let direction = Vec2::new(fireball_velocity.x, fireball_velocity.y)
+                .try_normalize()
+                .unwrap_or(Vec2::X);
+            commands
+                .entity(collision.enemy)
+                .insert(Hurt::new(knockback, direction));
+            continue;
+        }
 
-                    break;
-                }
-            }
+        // remove the enemy
+        *enemy_vitality = Vitality::Dead;
+        commands.entity(collision.enemy).despawn();
+
+        if boss.is_some() {
+            boss_defeated_events.send(BossDefeated {
+                killer: fireball_player_variant.clone(),
+                position: enemy_transform.translation,
+            });
+            continue;
         }
+
+        enemy_killed_events.send(EnemyKilled {
+            variant: enemy_variant.clone(),
+            killer: fireball_player_variant.clone(),
+            cause: KillCause::Fireball,
+            elite: elite.is_some(),
+            position: enemy_transform.translation,
+        });
     }
 }
 
 pub fn player_dead_spawn_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    enemy_query: Query<(Entity, &EntityLocation), With<PlayerDeadLocation>>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut player_died_events: EventReader<PlayerDied>,
 ) {
-    for (player_dead_entity, player_dead_location) in enemy_query.iter() {
-        // spawn the dead enemy sprite
+    let player_dead_sprite = sprite_registry.get("player_dead");
+    let death_clip = player_dead_sprite.clip("die").once();
+
+    for player_died in player_died_events.iter() {
+        // spawn the dead player sprite
         commands.spawn(PlayerDeadBundle {
             entity: PlayerDead,
-            animation_indices: AnimationIndices {
-                first: 10,
-                last: 13,
-            },
-            animation_timer: AnimationTimer::default(),
+            animation: AnimationController::new("die", death_clip.clone()),
             movable: Movable { auto_despawn: true },
             velocity: Velocity {
                 x: SCROLL_X_VELOCITY,
                 y: SCROLL_Y_VELOCITY,
             },
+            interpolated: Interpolated::default(),
             sprite_sheet: SpriteSheetBundle {
-                texture_atlas: game_textures.player_one_dead.clone(),
-                sprite: TextureAtlasSprite::new(11),
+                texture_atlas: player_dead_sprite.atlas(),
+                sprite: TextureAtlasSprite::new(death_clip.first),
                 transform: Transform {
-                    translation: Vec3::new(
-                        player_dead_location.x,
-                        player_dead_location.y,
-                        player_dead_location.z,
-                    ),
-                    scale: Vec3::splat(3.0),
+                    translation: player_died.position.truncate().extend(0.0),
+                    scale: Vec3::splat(player_dead_sprite.sheet.scale),
                     ..Default::default()
                 },
                 ..Default::default()
             },
         });
-        // despawn the PlayerDeadToSpawn
-        commands.entity(player_dead_entity).despawn();
     }
 }
 
 pub fn player_dead_movement_system(
     mut commands: Commands,
-    time: Res<Time>,
-    mut player_dead_query: Query<
-        (
-            Entity,
-            &mut AnimationTimer,
-            &AnimationIndices,
-            &mut TextureAtlasSprite,
-            &Velocity,
-            &mut Transform,
-            &Movable,
-        ),
-        With<PlayerDead>,
-    >,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut player_dead_query: Query<(Entity, &Velocity, &mut Transform, &Movable), With<PlayerDead>>,
+    playfield: Res<Playfield>,
 ) {
-    let window = window_query.get_single().unwrap();
-
     for (
         player_dead_entity,
-        mut player_dead_animation_timer,
-        player_dead_animation_indices,
-        mut player_dead_sprite,
         player_dead_velocity,
         mut player_dead_transform,
         player_dead_movable,
     ) in player_dead_query.iter_mut()
     {
-        if player_dead_sprite.index < player_dead_animation_indices.last {
-            animate_sprite_single(
-                &mut player_dead_sprite,
-                &player_dead_animation_indices,
-                &mut player_dead_animation_timer,
-                &time,
-            )
-        }
         let player_dead_translation = &mut player_dead_transform.translation;
         player_dead_translation.x -= player_dead_velocity.x * TIME_STEP * BASE_SPEED / 2.0 + 1.0;
 
         if player_dead_movable.auto_despawn {
             // despawn when out of screen
-            let window_margin = -window.width() / 2.0 - 20.0;
+            let window_margin = -playfield.width / 2.0 - 20.0;
             if player_dead_translation.x < window_margin {
                 commands.entity(player_dead_entity).despawn();
             }
@@ -493,34 +622,70 @@ pub fn player_dead_movement_system(
 
 pub fn player_hit_power_up_system(
     mut commands: Commands,
+    mut collision_events: EventReader<PlayerHitPowerUp>,
     mut player_query: Query<
-        (&mut Vitality, &Transform, &mut ItemPower),
-        (With<Player>, Without<PowerUp>),
+        (
+            &PlayerVariant,
+            &Vitality,
+            &mut Lives,
+            &mut ActiveEffects,
+            &mut Weapon,
+        ),
+        With<Player>,
     >,
-    power_up_query: Query<(Entity, &Transform), (With<PowerUp>, Without<Player>)>,
-    game_audio: Res<GameAudio>,
-    audio: Res<Audio>,
-    mut diamond_power_timer: ResMut<DiamondPowerTimer>,
+    power_up_query: Query<(&ItemVariant, &Transform, Option<&WeaponPickup>), With<PowerUp>>,
+    item_types: Res<ItemTypes>,
+    weapon_types: Res<WeaponTypes>,
+    mut power_up_events: EventWriter<PowerUpCollected>,
 ) {
-    for (power_up_entity, power_up_transform) in power_up_query.iter() {
-        for (player_vitality, player_transform, mut item_power) in player_query.iter_mut() {
-            if *player_vitality == Vitality::Alive {
-                let distance = player_transform
-                    .translation
-                    .distance(power_up_transform.translation);
-                let player_radius = PLAYER1_SPRITE.width / 2.0;
-                let power_up_radius = DIAMOND_SPRITE.width / 2.0;
-                if distance < player_radius + power_up_radius {
-                    item_power.diamond = true;
-                    commands.entity(power_up_entity).despawn();
-                    diamond_power_timer.timer.reset();
-                    audio
-                        .play(game_audio.diamond_powerup.clone())
-                        .with_volume(0.5)
-                        .fade_in(AudioTween::default());
-                    break;
+    // A power-up's hits arrive one after another, and only one player gets it.
+    let mut collected_power_up = None;
+
+    for collision in collision_events.iter() {
+        if collected_power_up == Some(collision.power_up) {
+            continue;
+        }
+        let Ok((player_variant, player_vitality, mut lives, mut active_effects, mut weapon)) =
+            player_query.get_mut(collision.player)
+        else {
+            continue;
+        };
+        let Ok((power_up_variant, power_up_transform, weapon_pickup)) =
+            power_up_query.get(collision.power_up)
+        else {
+            continue;
+        };
+
+        if *player_vitality == Vitality::Alive {
+            match (power_up_variant, weapon_pickup) {
+                (ItemVariant::Weapon, Some(weapon_pickup)) => {
+                    // a reload may have taken the weapon out of the manifest
+                    if let Some(weapon_type) = weapon_types.types.get(&weapon_pickup.kind) {
+                        info!("Player {} picked up {}", player_variant, weapon_type.name);
+                        *weapon = Weapon::new(&weapon_pickup.kind, weapon_type);
+                    }
+                }
+                (ItemVariant::Weapon, None) => {}
+                (ItemVariant::Item(item_key), _) => {
+                    // a reload may have taken the item out of the manifest
+                    if let Some(item_type) = item_types.types.get(item_key) {
+                        match item_type.effect {
+                            Some(Effect::ExtraLife) => lives.count += 1,
+                            Some(effect) => {
+                                active_effects.grant(effect, item_type.duration, item_type.stacking)
+                            }
+                            None => {}
+                        }
+                    }
                 }
             }
+            commands.entity(collision.power_up).despawn();
+            collected_power_up = Some(collision.power_up);
+            power_up_events.send(PowerUpCollected {
+                player: player_variant.clone(),
+                variant: power_up_variant.clone(),
+                position: power_up_transform.translation,
+            });
         }
     }
 }
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::common::components::{Damage, Interpolated, Movable, Velocity};
+use crate::common::resources::SpriteRegistry;
+use crate::player::bundles::FireballBundle;
+use crate::player::components::{Fireball, PlayerVariant};
+use crate::weapon::assets::{ChargeShot, WeaponType};
+use crate::weapon::components::{Bouncing, Piercing};
+
+/// Fire one shot of `weapon_type` from `translation` along the unit vector
+/// `aim`: a fan of `projectiles` spread evenly over the weapon's `spread`,
+/// made bigger and stronger by `charge` if it was charged up.
+pub fn spawn_shot(
+    commands: &mut Commands,
+    sprite_registry: &SpriteRegistry,
+    weapon_type: &WeaponType,
+    charge: Option<&ChargeShot>,
+    player_variant: &PlayerVariant,
+    translation: Vec3,
+    aim: Vec2,
+) {
+    let bullet_sprite = sprite_registry.get("bullet");
+
+    let (damage, scale, pierce) = match charge {
+        Some(charge) => (
+            weapon_type.damage * charge.damage_multiplier,
+            charge.scale,
+            weapon_type.pierce.max(charge.pierce),
+        ),
+        None => (weapon_type.damage, 1.0, weapon_type.pierce),
+    };
+
+    let projectiles = weapon_type.projectiles.max(1);
+    for index in 0..projectiles {
+        let angle = match projectiles {
+            1 => 0.0,
+            _ => weapon_type.spread * (index as f32 / (projectiles - 1) as f32 - 0.5),
+        };
+        let direction = Vec2::from_angle(angle.to_radians()).rotate(aim);
+
+        let mut fireball = commands.spawn(FireballBundle {
+            entity: Fireball,
+            variant: player_variant.clone(),
+            damage: Damage {
+                amount: damage,
+                knockback: weapon_type.knockback,
+            },
+            collider: weapon_type
+                .collider
+                .unwrap_or_else(|| bullet_sprite.collider()),
+            movable: Movable { auto_despawn: true },
+            velocity: Velocity {
+                x: direction.x * weapon_type.speed,
+                y: direction.y * weapon_type.speed,
+            },
+            interpolated: Interpolated::default(),
+            sprite_sheet: SpriteSheetBundle {
+                texture_atlas: bullet_sprite.atlas(),
+                sprite: TextureAtlasSprite::new(
+                    bullet_sprite.animation(&weapon_type.projectile).first,
+                ),
+                transform: Transform {
+                    translation,
+                    rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+                    scale: Vec3::splat(bullet_sprite.sheet.scale * scale),
+                },
+                ..Default::default()
+            },
+        });
+
+        if pierce > 0 {
+            fireball.insert(Piercing {
+                remaining: pierce,
+                hits: Vec::new(),
+            });
+        }
+        if weapon_type.bounces > 0 {
+            fireball.insert(Bouncing {
+                remaining: weapon_type.bounces,
+            });
+        }
+    }
+}
@@ -0,0 +1,56 @@
+pub mod resources;
+pub mod systems;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::common::TickSet;
+use crate::game::states::GameState;
+use crate::replay::resources::{ReplayPlayback, ReplayRecorder};
+use crate::replay::systems::{
+    replay_playback_system, replay_record_system, replay_recorder_start_system, replay_save_system,
+};
+
+pub const REPLAY_MAGIC: &[u8; 4] = b"BMRP";
+pub const REPLAY_VERSION: u8 = 4;
+
+/// Records every player's input per simulation tick, or plays a recording
+/// back in its place. Does nothing unless the app was built with a
+/// `ReplayRecorder` or `ReplayPlayback` resource.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(
+            replay_recorder_start_system
+                .in_base_set(StartupSet::PostStartup)
+                .run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .add_systems(
+            (
+                replay_playback_system.run_if(resource_exists::<ReplayPlayback>()),
+                replay_record_system.run_if(resource_exists::<ReplayRecorder>()),
+            )
+                .chain()
+                .distributive_run_if(in_state(GameState::Playing))
+                .in_set(TickSet::Begin)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        )
+        .add_system(
+            replay_save_system
+                .run_if(resource_exists::<ReplayRecorder>())
+                .in_schedule(OnEnter(GameState::GameOver)),
+        )
+        .add_system(
+            replay_save_system
+                .run_if(resource_exists::<ReplayRecorder>())
+                .in_schedule(OnEnter(GameState::Victory)),
+        )
+        .add_system(
+            replay_save_system
+                .run_if(resource_exists::<ReplayRecorder>())
+                .run_if(on_event::<AppExit>())
+                .in_base_set(CoreSet::Last),
+        );
+    }
+}
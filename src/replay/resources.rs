@@ -0,0 +1,283 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::ActionState;
+use leafwing_input_manager::Actionlike;
+
+use crate::common::resources::Playfield;
+use crate::difficulty::resources::DifficultyPreset;
+use crate::game::resources::GameMode;
+use crate::player::actions::ControlAction;
+use crate::player::components::{PlayerInput, PlayerVariant};
+use crate::replay::{REPLAY_MAGIC, REPLAY_VERSION};
+
+pub const REPLAY_PLAYERS: usize = 2;
+
+const FLAG_FIRE: u8 = 1 << 0;
+const FLAG_FIRE_PRESSED: u8 = 1 << 1;
+const FLAG_RESTART_PRESSED: u8 = 1 << 2;
+const FLAG_MOVEMENT: u8 = 1 << 3;
+const FLAG_AIM: u8 = 1 << 4;
+
+/// One player's controls for one simulation tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayInput {
+    /// `ControlAction`s held down, one bit per action index.
+    pub actions: u16,
+    /// What the tick actually consumed.
+    pub input: PlayerInput,
+}
+
+impl ReplayInput {
+    pub fn capture(action_state: &ActionState<ControlAction>, input: &PlayerInput) -> Self {
+        let actions = ControlAction::variants()
+            .filter(|action| action_state.pressed(*action))
+            .fold(0, |actions, action| actions | 1 << action.index());
+
+        Self {
+            actions,
+            input: *input,
+        }
+    }
+
+    pub fn pressed(&self, action: ControlAction) -> bool {
+        self.actions & 1 << action.index() != 0
+    }
+}
+
+/// A recorded run: the seed, mode, difficulty and playfield it started from,
+/// and every player's input for each tick spent in `GameState::Playing`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub game_mode: GameMode,
+    pub difficulty: DifficultyPreset,
+    pub playfield: Playfield,
+    pub ticks: Vec<[ReplayInput; REPLAY_PLAYERS]>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, game_mode: GameMode, difficulty: DifficultyPreset) -> Self {
+        Self {
+            seed,
+            game_mode,
+            difficulty,
+            playfield: Playfield::default(),
+            ticks: Vec::new(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode(&fs::read(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(32 + self.ticks.len() * REPLAY_PLAYERS * 3);
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.push(REPLAY_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.push(match self.game_mode {
+            GameMode::Solo => 0,
+            GameMode::Coop => 1,
+        });
+        bytes.push(match self.difficulty {
+            DifficultyPreset::Easy => 0,
+            DifficultyPreset::Normal => 1,
+            DifficultyPreset::Hard => 2,
+        });
+        bytes.extend_from_slice(&self.playfield.width.to_le_bytes());
+        bytes.extend_from_slice(&self.playfield.height.to_le_bytes());
+        bytes.extend_from_slice(&(self.ticks.len() as u32).to_le_bytes());
+
+        for tick in self.ticks.iter() {
+            for replay_input in tick.iter() {
+                let input = &replay_input.input;
+                let mut flags = 0;
+                if input.fire {
+                    flags |= FLAG_FIRE;
+                }
+                if input.fire_pressed {
+                    flags |= FLAG_FIRE_PRESSED;
+                }
+                if input.restart_pressed {
+                    flags |= FLAG_RESTART_PRESSED;
+                }
+                if input.movement != Vec2::ZERO {
+                    flags |= FLAG_MOVEMENT;
+                }
+                if input.aim != Vec2::ZERO {
+                    flags |= FLAG_AIM;
+                }
+
+                bytes.extend_from_slice(&replay_input.actions.to_le_bytes());
+                bytes.push(flags);
+                if flags & FLAG_MOVEMENT != 0 {
+                    bytes.extend_from_slice(&input.movement.x.to_le_bytes());
+                    bytes.extend_from_slice(&input.movement.y.to_le_bytes());
+                }
+                if flags & FLAG_AIM != 0 {
+                    bytes.extend_from_slice(&input.aim.x.to_le_bytes());
+                    bytes.extend_from_slice(&input.aim.y.to_le_bytes());
+                }
+            }
+        }
+
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = ByteReader(bytes);
+
+        if reader.take(REPLAY_MAGIC.len())? != REPLAY_MAGIC {
+            return Err(invalid_data("not a replay file"));
+        }
+        let version = reader.u8()?;
+        if version != REPLAY_VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported replay version {}",
+                version
+            )));
+        }
+
+        let seed = reader.u64()?;
+        let game_mode = match reader.u8()? {
+            0 => GameMode::Solo,
+            1 => GameMode::Coop,
+            game_mode => return Err(invalid_data(&format!("unknown game mode {}", game_mode))),
+        };
+        let difficulty = match reader.u8()? {
+            0 => DifficultyPreset::Easy,
+            1 => DifficultyPreset::Normal,
+            2 => DifficultyPreset::Hard,
+            difficulty => return Err(invalid_data(&format!("unknown difficulty {}", difficulty))),
+        };
+        let playfield = Playfield::new(reader.f32()?, reader.f32()?);
+        let tick_count = reader.u32()? as usize;
+
+        let mut ticks = Vec::with_capacity(tick_count);
+        for _ in 0..tick_count {
+            let mut tick = [ReplayInput::default(); REPLAY_PLAYERS];
+            for replay_input in tick.iter_mut() {
+                replay_input.actions = reader.u16()?;
+                let flags = reader.u8()?;
+                replay_input.input = PlayerInput {
+                    movement: if flags & FLAG_MOVEMENT != 0 {
+                        Vec2::new(reader.f32()?, reader.f32()?)
+                    } else {
+                        Vec2::ZERO
+                    },
+                    aim: if flags & FLAG_AIM != 0 {
+                        Vec2::new(reader.f32()?, reader.f32()?)
+                    } else {
+                        Vec2::ZERO
+                    },
+                    fire: flags & FLAG_FIRE != 0,
+                    fire_pressed: flags & FLAG_FIRE_PRESSED != 0,
+                    restart_pressed: flags & FLAG_RESTART_PRESSED != 0,
+                };
+            }
+            ticks.push(tick);
+        }
+
+        Ok(Self {
+            seed,
+            game_mode,
+            difficulty,
+            playfield,
+            ticks,
+        })
+    }
+}
+
+/// Which slot of a replay tick belongs to a player.
+pub fn replay_slot(player_variant: &PlayerVariant) -> usize {
+    match player_variant {
+        PlayerVariant::One => 0,
+        PlayerVariant::Two => 1,
+    }
+}
+
+/// Records the current run, written to `path` on game over or exit.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+    pub finished: bool,
+}
+
+impl ReplayRecorder {
+    pub fn new(
+        path: impl Into<PathBuf>,
+        seed: u64,
+        game_mode: GameMode,
+        difficulty: DifficultyPreset,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            replay: Replay::new(seed, game_mode, difficulty),
+            finished: false,
+        }
+    }
+}
+
+/// Feeds a recorded run back in place of live input.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub tick: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, tick: 0 }
+    }
+}
+
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < count {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        let (taken, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> io::Result<f32> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
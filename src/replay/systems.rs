@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::plugin::ToggleActions;
+use leafwing_input_manager::prelude::ActionState;
+use leafwing_input_manager::Actionlike;
+
+use crate::common::resources::Playfield;
+use crate::player::actions::ControlAction;
+use crate::player::components::{Player, PlayerInput, PlayerVariant};
+use crate::replay::resources::{
+    replay_slot, ReplayInput, ReplayPlayback, ReplayRecorder, REPLAY_PLAYERS,
+};
+
+/// Remember the playfield the opening layout was spawned on.
+pub fn replay_recorder_start_system(
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    playfield: Res<Playfield>,
+) {
+    replay_recorder.replay.playfield = *playfield;
+}
+
+pub fn replay_record_system(
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    player_query: Query<(&PlayerVariant, &ActionState<ControlAction>, &PlayerInput), With<Player>>,
+) {
+    if replay_recorder.finished {
+        return;
+    }
+
+    let mut tick = [ReplayInput::default(); REPLAY_PLAYERS];
+    for (player_variant, action_state, player_input) in player_query.iter() {
+        tick[replay_slot(player_variant)] = ReplayInput::capture(action_state, player_input);
+    }
+
+    replay_recorder.replay.ticks.push(tick);
+}
+
+/// Replace this tick's input with the recorded one. Live input stays
+/// disabled until the recording runs out.
+pub fn replay_playback_system(
+    mut commands: Commands,
+    mut replay_playback: ResMut<ReplayPlayback>,
+    mut player_query: Query<
+        (
+            &PlayerVariant,
+            &mut ActionState<ControlAction>,
+            &mut PlayerInput,
+        ),
+        With<Player>,
+    >,
+    mut toggle_actions: ResMut<ToggleActions<ControlAction>>,
+    time: Res<Time>,
+) {
+    let Some(tick) = replay_playback
+        .replay
+        .ticks
+        .get(replay_playback.tick)
+        .copied()
+    else {
+        info!("Replay finished after {} ticks", replay_playback.tick);
+        *toggle_actions = ToggleActions::ENABLED;
+        commands.remove_resource::<ReplayPlayback>();
+        return;
+    };
+    replay_playback.tick += 1;
+
+    let now = time.last_update().unwrap_or_else(|| time.startup());
+
+    for (player_variant, mut action_state, mut player_input) in player_query.iter_mut() {
+        let recorded = tick[replay_slot(player_variant)];
+
+        action_state.tick(now, now);
+        for action in ControlAction::variants() {
+            if recorded.pressed(action) {
+                action_state.press(action);
+            } else {
+                action_state.release(action);
+            }
+        }
+        if recorded.pressed(ControlAction::AxisMove) {
+            action_state
+                .action_data_mut(ControlAction::AxisMove)
+                .axis_pair = Some(DualAxisData::from_xy(recorded.input.movement));
+        }
+        if recorded.pressed(ControlAction::AxisAim) {
+            action_state
+                .action_data_mut(ControlAction::AxisAim)
+                .axis_pair = Some(DualAxisData::from_xy(recorded.input.aim));
+        }
+
+        *player_input = recorded.input;
+    }
+}
+
+/// Write the recording out once, when the run ends.
+pub fn replay_save_system(mut replay_recorder: ResMut<ReplayRecorder>) {
+    if replay_recorder.finished || replay_recorder.replay.ticks.is_empty() {
+        return;
+    }
+    replay_recorder.finished = true;
+
+    match replay_recorder.replay.save(&replay_recorder.path) {
+        Ok(()) => info!(
+            "Saved replay of {} ticks to {}",
+            replay_recorder.replay.ticks.len(),
+            replay_recorder.path.display()
+        ),
+        Err(error) => error!(
+            "Failed to save replay to {}: {}",
+            replay_recorder.path.display(),
+            error
+        ),
+    }
+}
@@ -1,9 +1,56 @@
+pub mod resources;
+pub mod systems;
+pub mod utils;
+
 use bevy::prelude::*;
 
+use crate::game::states::GameState;
+use crate::game::systems::exit_game_system;
+use crate::game::GameEventSystemSet;
+use crate::replay::resources::ReplayPlayback;
+use crate::score::resources::{HighScoreFile, HighScores, Initials, InitialsEntry};
+use crate::score::systems::{
+    enemy_killed_score_system, high_score_load_system, initials_entry_start_system,
+    initials_entry_system, power_up_score_system,
+};
+
+/// How many entries are kept in each high-score table.
+pub const HIGH_SCORE_TABLE_SIZE: usize = 10;
+pub const HIGH_SCORE_FILE_NAME: &str = "high_scores.ron";
+pub const INITIALS_LENGTH: usize = 3;
+
 pub struct ScorePlugin;
 
 impl Plugin for ScorePlugin {
-    fn build(&self, _app: &mut App) {
-        // _app;
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HighScoreFile>()
+            .init_resource::<HighScores>()
+            .init_resource::<Initials>()
+            .add_startup_system(high_score_load_system)
+            .add_system(
+                enemy_killed_score_system
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                power_up_score_system
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                initials_entry_start_system
+                    .run_if(not(resource_exists::<ReplayPlayback>()))
+                    .in_schedule(OnEnter(GameState::GameOver)),
+            )
+            .add_system(
+                initials_entry_start_system
+                    .run_if(not(resource_exists::<ReplayPlayback>()))
+                    .in_schedule(OnEnter(GameState::Victory)),
+            )
+            .add_system(
+                initials_entry_system
+                    .after(exit_game_system)
+                    .run_if(resource_exists::<InitialsEntry>()),
+            );
     }
 }
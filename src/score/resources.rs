@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::resources::GameMode;
+use crate::player::components::PlayerVariant;
+use crate::score::utils::user_data_dir;
+use crate::score::{HIGH_SCORE_FILE_NAME, HIGH_SCORE_TABLE_SIZE, INITIALS_LENGTH};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HighScoreEntry {
+    pub initials: String,
+    pub score: u32,
+    /// Day the score was set on, as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+/// The best scores ever reached, kept separately for each `GameMode`.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HighScores {
+    pub tables: BTreeMap<GameMode, Vec<HighScoreEntry>>,
+}
+
+impl HighScores {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        ron::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(path, contents)
+    }
+
+    pub fn table(&self, game_mode: GameMode) -> &[HighScoreEntry] {
+        self.tables
+            .get(&game_mode)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Where `score` would go in the table for `game_mode`, if it makes it.
+    /// A new score ranks below older equal ones.
+    pub fn rank(&self, game_mode: GameMode, score: u32) -> Option<usize> {
+        let table = self.table(game_mode);
+        let rank = table
+            .iter()
+            .position(|existing| existing.score < score)
+            .unwrap_or(table.len());
+
+        (rank < HIGH_SCORE_TABLE_SIZE).then_some(rank)
+    }
+
+    /// Add a score to the table for `game_mode`, returning its rank if it
+    /// made the table.
+    pub fn insert(&mut self, game_mode: GameMode, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.rank(game_mode, entry.score)?;
+
+        let table = self.tables.entry(game_mode).or_default();
+        table.insert(rank, entry);
+        table.truncate(HIGH_SCORE_TABLE_SIZE);
+        Some(rank)
+    }
+}
+
+/// Where the high-score table is read from and written to. With no path
+/// the table only lives as long as the app.
+#[derive(Resource, Clone, Debug)]
+pub struct HighScoreFile {
+    pub path: Option<PathBuf>,
+}
+
+impl Default for HighScoreFile {
+    fn default() -> Self {
+        Self {
+            path: user_data_dir().map(|dir| dir.join(HIGH_SCORE_FILE_NAME)),
+        }
+    }
+}
+
+/// The initials each player's scores are entered under. They can be set
+/// before a game, with `--initials ABC` or `--initials ABC,XYZ`, and are
+/// replaced by any a player types in when their score makes the table.
+#[derive(Resource, Clone, Debug)]
+pub struct Initials {
+    pub one: String,
+    pub two: String,
+}
+
+impl Initials {
+    pub fn new(one: &str, two: &str) -> Self {
+        Self {
+            one: Self::normalize(one),
+            two: Self::normalize(two),
+        }
+    }
+
+    /// Up to `INITIALS_LENGTH` letters or digits, upper-cased.
+    pub fn normalize(initials: &str) -> String {
+        initials
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .take(INITIALS_LENGTH)
+            .collect::<String>()
+            .to_ascii_uppercase()
+    }
+
+    pub fn get(&self, player_variant: &PlayerVariant) -> &str {
+        match player_variant {
+            PlayerVariant::One => &self.one,
+            PlayerVariant::Two => &self.two,
+        }
+    }
+
+    pub fn set(&mut self, player_variant: &PlayerVariant, initials: &str) {
+        let initials = Self::normalize(initials);
+        match player_variant {
+            PlayerVariant::One => self.one = initials,
+            PlayerVariant::Two => self.two = initials,
+        }
+    }
+}
+
+impl Default for Initials {
+    fn default() -> Self {
+        Self::new("P1", "P2")
+    }
+}
+
+/// A final score that makes the table, waiting for its player's initials.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingScore {
+    pub player_variant: PlayerVariant,
+    pub score: u32,
+}
+
+/// Initials being typed in at the end of a game, one player at a time. The
+/// first pending score is the one being entered.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct InitialsEntry {
+    pub pending: Vec<PendingScore>,
+    pub typed: String,
+}
@@ -0,0 +1,190 @@
+use std::io;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::{DIAMOND_SCORE_MULTIPLIER, ELITE_SCORE_MULTIPLIER};
+use crate::game::events::{EnemyKilled, KillCause, PowerUpCollected};
+use crate::game::resources::GameMode;
+use crate::item::components::ItemVariant;
+use crate::item::resources::ItemTypes;
+use crate::player::components::{Player, PlayerVariant, Score};
+use crate::score::resources::{
+    HighScoreEntry, HighScoreFile, HighScores, Initials, InitialsEntry, PendingScore,
+};
+use crate::score::utils::{initials_char, today};
+use crate::score::INITIALS_LENGTH;
+
+/// Award the points for each kill to the player who made it.
+pub fn enemy_killed_score_system(
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+    mut player_query: Query<(&PlayerVariant, &mut Score), With<Player>>,
+    enemy_types: Res<EnemyTypes>,
+) {
+    for enemy_killed in enemy_killed_events.iter() {
+        // a reload may have taken the enemy out of the manifest
+        let Some(enemy_type) = enemy_types.types.get(&enemy_killed.variant.0) else {
+            continue;
+        };
+        let mut points = enemy_type.score;
+        if enemy_killed.cause == KillCause::Diamond {
+            points *= DIAMOND_SCORE_MULTIPLIER;
+        }
+        if enemy_killed.elite {
+            points *= ELITE_SCORE_MULTIPLIER;
+        }
+
+        for (player_variant, mut player_score) in player_query.iter_mut() {
+            if *player_variant == enemy_killed.killer {
+                player_score.value += points;
+            }
+        }
+    }
+}
+
+/// Award the points an item is worth to the player who picked it up.
+pub fn power_up_score_system(
+    mut power_up_events: EventReader<PowerUpCollected>,
+    mut player_query: Query<(&PlayerVariant, &mut Score), With<Player>>,
+    item_types: Res<ItemTypes>,
+) {
+    for power_up in power_up_events.iter() {
+        let ItemVariant::Item(item_key) = &power_up.variant else {
+            continue;
+        };
+        // a reload may have taken the item out of the manifest
+        let Some(item_type) = item_types.types.get(item_key) else {
+            continue;
+        };
+        let points = item_type.score;
+
+        for (player_variant, mut player_score) in player_query.iter_mut() {
+            if *player_variant == power_up.player {
+                player_score.value += points;
+            }
+        }
+    }
+}
+
+pub fn high_score_load_system(
+    high_score_file: Res<HighScoreFile>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    let Some(path) = &high_score_file.path else {
+        return;
+    };
+
+    match HighScores::load(path) {
+        Ok(loaded) => *high_scores = loaded,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => warn!(
+            "Failed to load high scores from {}: {}",
+            path.display(),
+            error
+        ),
+    }
+}
+
+/// Line up each player whose final score makes the table for the current
+/// mode to type in their initials, player one first.
+pub fn initials_entry_start_system(
+    mut commands: Commands,
+    player_query: Query<(&PlayerVariant, &Score), With<Player>>,
+    game_mode: Res<GameMode>,
+    high_scores: Res<HighScores>,
+) {
+    let mut pending: Vec<_> = player_query
+        .iter()
+        .filter(|(_, player_score)| {
+            player_score.value > 0 && high_scores.rank(*game_mode, player_score.value).is_some()
+        })
+        .map(|(player_variant, player_score)| PendingScore {
+            player_variant: player_variant.clone(),
+            score: player_score.value,
+        })
+        .collect();
+
+    if pending.is_empty() {
+        return;
+    }
+
+    pending.sort_by_key(|pending_score| pending_score.player_variant == PlayerVariant::Two);
+    commands.insert_resource(InitialsEntry {
+        pending,
+        typed: String::new(),
+    });
+}
+
+/// Type in the initials for the first pending score: letters and digits add
+/// on, Backspace takes one off and Enter puts the score in the table. Enter
+/// with nothing typed keeps the player's current initials. Leaving the game
+/// enters every pending score as it stands.
+#[allow(clippy::too_many_arguments)]
+pub fn initials_entry_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut app_exit_events: EventReader<AppExit>,
+    game_mode: Res<GameMode>,
+    high_score_file: Res<HighScoreFile>,
+    mut initials_entry: ResMut<InitialsEntry>,
+    mut initials: ResMut<Initials>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    for key_code in keyboard_input.get_just_pressed() {
+        if *key_code == KeyCode::Back {
+            initials_entry.typed.pop();
+        } else if let Some(character) = initials_char(*key_code) {
+            if initials_entry.typed.len() < INITIALS_LENGTH {
+                initials_entry.typed.push(character);
+            }
+        }
+    }
+
+    let entered = if app_exit_events.iter().count() > 0 {
+        initials_entry.pending.len()
+    } else if keyboard_input.just_pressed(KeyCode::Return) {
+        1
+    } else {
+        return;
+    };
+
+    let date = today();
+    let entering: Vec<_> = initials_entry.pending.drain(..entered).collect();
+    for pending_score in entering {
+        if !initials_entry.typed.is_empty() {
+            initials.set(&pending_score.player_variant, &initials_entry.typed);
+        }
+        initials_entry.typed.clear();
+
+        let entry = HighScoreEntry {
+            initials: initials.get(&pending_score.player_variant).to_string(),
+            score: pending_score.score,
+            date: date.clone(),
+        };
+
+        // the other player's score may have pushed this one off the table
+        if let Some(rank) = high_scores.insert(*game_mode, entry) {
+            info!(
+                "Player {} made the {} high scores at #{}",
+                pending_score.player_variant,
+                game_mode.label(),
+                rank + 1
+            );
+        }
+    }
+
+    if initials_entry.pending.is_empty() {
+        commands.remove_resource::<InitialsEntry>();
+    }
+
+    if let Some(path) = &high_score_file.path {
+        if let Err(error) = high_scores.save(path) {
+            error!(
+                "Failed to save high scores to {}: {}",
+                path.display(),
+                error
+            );
+        }
+    }
+}
@@ -9,5 +9,12 @@ pub struct PlayButton;
 #[derive(Component)]
 pub struct QuitButton;
 
+#[derive(Component)]
+pub struct HighScoreTable;
+
+/// Asks a player whose score made the table for their initials.
+#[derive(Component)]
+pub struct InitialsPrompt;
+
 #[derive(Component)]
 pub struct HUD;
@@ -6,7 +6,9 @@ pub mod utils;
 use bevy::prelude::*;
 
 use crate::game::states::AppState;
+use crate::score::resources::HighScores;
 use crate::ui::systems::{
+    high_score_table_update_system, initials_prompt_update_system,
     interact_with_play_button_system, interact_with_quit_button_system, main_menu_despawn_system,
     main_menu_spawn_system,
 };
@@ -21,6 +23,8 @@ impl Plugin for UIPlugin {
                 (
                     interact_with_play_button_system,
                     interact_with_quit_button_system,
+                    high_score_table_update_system.run_if(resource_changed::<HighScores>()),
+                    initials_prompt_update_system,
                 )
                     .in_set(OnUpdate(AppState::MainMenu)),
             );
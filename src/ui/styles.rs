@@ -26,6 +26,14 @@ pub const IMAGE_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+pub const HIGH_SCORE_TABLE_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    margin: UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(16.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
 pub const TITLE_STYLE: Style = Style {
     flex_direction: FlexDirection::Row,
     justify_content: JustifyContent::Center,
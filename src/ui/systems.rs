@@ -1,16 +1,26 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
 
+use crate::game::resources::GameMode;
 use crate::game::states::AppState;
-use crate::ui::components::{MainMenu, PlayButton, QuitButton};
+use crate::score::resources::{HighScores, Initials, InitialsEntry};
+use crate::ui::components::{HighScoreTable, InitialsPrompt, MainMenu, PlayButton, QuitButton};
 use crate::ui::styles::{
-    BUTTON_STYLE, HOVERED_BUTTON_COLOR, MAIN_MENU_STYLE, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR,
-    TITLE_STYLE,
+    BUTTON_STYLE, HIGH_SCORE_TABLE_STYLE, HOVERED_BUTTON_COLOR, MAIN_MENU_STYLE,
+    NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR, TITLE_STYLE,
+};
+use crate::ui::utils::{
+    get_high_score_text_style, get_initials_prompt_text_style, get_title_text_style,
+    high_score_table_text, initials_prompt_text,
 };
-use crate::ui::utils::get_title_text_style;
 
-pub fn main_menu_spawn_system(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let _main_menu_entity = build_main_menu(&mut commands, &asset_server);
+pub fn main_menu_spawn_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    game_mode: Res<GameMode>,
+) {
+    let _main_menu_entity = build_main_menu(&mut commands, &asset_server, &high_scores, *game_mode);
 }
 
 pub fn main_menu_despawn_system(
@@ -22,7 +32,12 @@ pub fn main_menu_despawn_system(
     }
 }
 
-pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+pub fn build_main_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    high_scores: &HighScores,
+    game_mode: GameMode,
+) -> Entity {
     let main_menu_entity = commands
         .spawn((
             NodeBundle {
@@ -43,7 +58,7 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         text: Text {
                             sections: vec![TextSection::new(
                                 "Monster Apocalypse",
-                                get_title_text_style(&asset_server),
+                                get_title_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -71,7 +86,6 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                     font_size: 32.0,
                                     color: Color::WHITE,
-                                    ..Default::default()
                                 },
                             )],
                             alignment: TextAlignment::Center,
@@ -99,7 +113,6 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                                     font_size: 32.0,
                                     color: Color::WHITE,
-                                    ..Default::default()
                                 },
                             )],
                             alignment: TextAlignment::Center,
@@ -108,12 +121,50 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         ..Default::default()
                     });
                 });
+
+            // High scores
+            parent
+                .spawn(NodeBundle {
+                    style: HIGH_SCORE_TABLE_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    "",
+                                    get_initials_prompt_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        InitialsPrompt,
+                    ));
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    high_score_table_text(high_scores, game_mode),
+                                    get_high_score_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        HighScoreTable,
+                    ));
+                });
         })
         .id();
 
     main_menu_entity
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_with_play_button_system(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor),
@@ -137,6 +188,7 @@ pub fn interact_with_play_button_system(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_with_quit_button_system(
     mut app_exit_event_writer: EventWriter<AppExit>,
     mut button_query: Query<
@@ -159,3 +211,31 @@ pub fn interact_with_quit_button_system(
         }
     }
 }
+
+/// The table can change while the menu is open, e.g. when the game over
+/// that opened it is recorded after the menu was built.
+pub fn high_score_table_update_system(
+    mut table_query: Query<&mut Text, With<HighScoreTable>>,
+    high_scores: Res<HighScores>,
+    game_mode: Res<GameMode>,
+) {
+    for mut text in table_query.iter_mut() {
+        text.sections[0].value = high_score_table_text(&high_scores, *game_mode);
+    }
+}
+
+/// Keep the prompt in step with the initials being typed in, and empty
+/// once they are all entered.
+pub fn initials_prompt_update_system(
+    mut prompt_query: Query<&mut Text, With<InitialsPrompt>>,
+    initials_entry: Option<Res<InitialsEntry>>,
+    initials: Res<Initials>,
+) {
+    let prompt = initials_prompt_text(initials_entry.as_deref(), &initials);
+
+    for mut text in prompt_query.iter_mut() {
+        if text.sections[0].value != prompt {
+            text.sections[0].value = prompt.clone();
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 
+use crate::game::resources::GameMode;
+use crate::score::resources::{HighScores, Initials, InitialsEntry};
+use crate::score::INITIALS_LENGTH;
+
 pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     TextStyle {
         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
@@ -7,3 +11,68 @@ pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
         color: Color::WHITE,
     }
 }
+
+pub fn get_high_score_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 24.0,
+        color: Color::WHITE,
+    }
+}
+
+pub fn get_initials_prompt_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 32.0,
+        color: Color::GOLD,
+    }
+}
+
+pub fn high_score_table_text(high_scores: &HighScores, game_mode: GameMode) -> String {
+    let mut text = format!("{} High Scores", game_mode.label());
+
+    let table = high_scores.table(game_mode);
+    if table.is_empty() {
+        text.push_str("\n---");
+    }
+    for (rank, entry) in table.iter().enumerate() {
+        text.push_str(&format!(
+            "\n{:>2}. {:<3} {:>6}  {}",
+            rank + 1,
+            entry.initials,
+            entry.score,
+            entry.date
+        ));
+    }
+
+    text
+}
+
+/// Who is typing in their initials and what they have so far, or nothing
+/// when no one is.
+pub fn initials_prompt_text(initials_entry: Option<&InitialsEntry>, initials: &Initials) -> String {
+    let Some(initials_entry) = initials_entry else {
+        return String::new();
+    };
+    let Some(pending_score) = initials_entry.pending.first() else {
+        return String::new();
+    };
+
+    let enter = if initials_entry.typed.is_empty() {
+        format!(
+            "Enter to keep {}",
+            initials.get(&pending_score.player_variant)
+        )
+    } else {
+        "Enter to save".to_string()
+    };
+
+    format!(
+        "Player {} made the high scores with {}!\nInitials: {:_<width$}\n{}",
+        pending_score.player_variant,
+        pending_score.score,
+        initials_entry.typed,
+        enter,
+        width = INITIALS_LENGTH
+    )
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// A one-off explosion, removed once its animation has played.
+#[derive(Component)]
+pub struct Explosion;
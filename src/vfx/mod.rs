@@ -0,0 +1,27 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::animation::AnimationSystemSet;
+use crate::common::TickSet;
+use crate::game::GameEventSystemSet;
+use crate::vfx::systems::{explosion_despawn_system, explosion_spawn_system};
+
+pub struct VfxPlugin;
+
+impl Plugin for VfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            explosion_spawn_system
+                .in_set(GameEventSystemSet)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        )
+        .add_system(
+            explosion_despawn_system
+                .after(AnimationSystemSet)
+                .before(TickSet::End)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
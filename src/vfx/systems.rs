@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::animation::components::AnimationController;
+use crate::animation::events::AnimationFinished;
+use crate::common::resources::SpriteRegistry;
+use crate::game::events::EnemyKilled;
+use crate::vfx::components::Explosion;
+
+pub fn explosion_spawn_system(
+    mut commands: Commands,
+    sprite_registry: Res<SpriteRegistry>,
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+) {
+    let explosion_sprite = sprite_registry.get("explosion");
+    let explode_clip = explosion_sprite.clip("explode").once();
+
+    for enemy_killed in enemy_killed_events.iter() {
+        commands.spawn((
+            Explosion,
+            AnimationController::new("explode", explode_clip.clone()),
+            SpriteSheetBundle {
+                texture_atlas: explosion_sprite.atlas(),
+                sprite: TextureAtlasSprite::new(explode_clip.first),
+                transform: Transform {
+                    translation: enemy_killed.position.truncate().extend(20.0),
+                    scale: Vec3::splat(explosion_sprite.sheet.scale),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+pub fn explosion_despawn_system(
+    mut commands: Commands,
+    mut animation_finished_events: EventReader<AnimationFinished>,
+    explosion_query: Query<(), With<Explosion>>,
+) {
+    for animation_finished in animation_finished_events.iter() {
+        if explosion_query.contains(animation_finished.entity) {
+            commands.entity(animation_finished.entity).despawn();
+        }
+    }
+}
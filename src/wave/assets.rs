@@ -0,0 +1,111 @@
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::wave::resources::WaveScripts;
+use crate::wave::WAVE_MANIFEST;
+
+/// The waves a match is made of, in order. Loaded from
+/// `assets/manifest.waves.ron`.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "5d6f8a3e-2c1b-4e0f-9a7d-3b8c6e1f4a25"]
+pub struct WaveManifest {
+    pub waves: Vec<WaveScript>,
+    /// Index of the wave to carry on from once the last one is cleared.
+    #[serde(default)]
+    pub loop_from: usize,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WaveScript {
+    pub groups: Vec<SpawnGroup>,
+    pub clear: ClearCondition,
+    /// Seconds of quiet once the wave is cleared, before the next one.
+    pub pause: f32,
+}
+
+/// A batch of one type of enemy, spawned together in a formation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpawnGroup {
+    /// `EnemyTypes` key.
+    pub enemy: String,
+    pub count: u32,
+    #[serde(default)]
+    pub side: SpawnSide,
+    #[serde(default)]
+    pub formation: Formation,
+    /// Seconds after the wave starts that the group comes in.
+    #[serde(default)]
+    pub delay: f32,
+}
+
+/// Which stretch of the right edge a group comes in along.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpawnSide {
+    #[default]
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Formation {
+    /// Spread out at random, a little way off screen.
+    #[default]
+    Scatter,
+    /// Side by side, top to bottom.
+    Line,
+    /// One behind the other.
+    Column,
+    /// An arrowhead pointing at the players.
+    Wedge,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ClearCondition {
+    /// Every enemy is gone, killed or got past.
+    NoEnemiesLeft,
+    /// This many seconds after the wave starts, whatever is left.
+    Timeout(f32),
+}
+
+impl WaveScript {
+    /// What, if anything, stops the wave from being played.
+    fn check(&self) -> Result<(), String> {
+        let timeout = match self.clear {
+            ClearCondition::Timeout(seconds) => seconds,
+            ClearCondition::NoEnemiesLeft => 0.0,
+        };
+        let delays = self.groups.iter().map(|group| group.delay);
+        for seconds in [self.pause, timeout].into_iter().chain(delays) {
+            if !seconds.is_finite() || seconds < 0.0 {
+                return Err(format!("waits for {} seconds", seconds));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RonManifest for WaveManifest {
+    const PATH: &'static str = WAVE_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["waves.ron"];
+}
+
+impl ManifestResource for WaveManifest {
+    type Resource = WaveScripts;
+
+    fn build(&self) -> Result<WaveScripts, String> {
+        if self.waves.is_empty() {
+            return Err("no waves".to_string());
+        }
+        for (index, wave) in self.waves.iter().enumerate() {
+            wave.check()
+                .map_err(|problem| format!("wave {} {}", index, problem))?;
+        }
+
+        Ok(WaveScripts {
+            waves: self.waves.clone(),
+            loop_from: self.loop_from,
+        })
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// The "Wave N" banner shown when a wave starts. Fades out, then despawns.
+#[derive(Component)]
+pub struct WaveAnnouncement {
+    pub timer: Timer,
+}
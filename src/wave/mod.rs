@@ -0,0 +1,71 @@
+pub mod assets;
+pub mod components;
+pub mod resources;
+pub mod systems;
+pub mod utils;
+
+use bevy::prelude::*;
+
+use crate::common::ManifestPlugin;
+use crate::common::TickSet;
+use crate::enemy::boss::systems::boss_encounter_waiting;
+use crate::enemy::EnemySystemSet;
+use crate::game::states::GameState;
+use crate::game::GameEventSystemSet;
+use crate::item::ItemSystemSet;
+use crate::wave::assets::WaveManifest;
+use crate::wave::resources::WaveDirector;
+use crate::wave::systems::{
+    wave_announcement_fade_system, wave_announcement_spawn_system, wave_director_system,
+    wave_scripts_check_system,
+};
+
+pub const WAVE_MANIFEST: &str = "manifest.waves.ron";
+/// Seconds from the start of a match to the first wave.
+pub const FIRST_WAVE_DELAY: f32 = 1.0;
+/// Seconds the "Wave N" banner stays up for.
+pub const WAVE_ANNOUNCEMENT_TIME: f32 = 2.0;
+/// Distance between neighbours in a `Column` or `Wedge` formation.
+pub const FORMATION_SPACING: f32 = 80.0;
+
+/// Brings in the enemies for each wave, once the enemies already out there
+/// have moved for the tick.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct WaveSystemSet;
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveDirector>()
+            .add_plugin(ManifestPlugin::<WaveManifest>::default())
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    WaveSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(EnemySystemSet)
+                        .before(ItemSystemSet)
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
+            .add_systems(
+                (
+                    wave_scripts_check_system,
+                    wave_director_system.run_if(boss_encounter_waiting),
+                )
+                    .chain()
+                    .in_set(WaveSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_systems(
+                (
+                    wave_announcement_spawn_system,
+                    wave_announcement_fade_system,
+                )
+                    .chain()
+                    .in_set(GameEventSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::wave::assets::WaveScript;
+use crate::wave::{FIRST_WAVE_DELAY, WAVE_MANIFEST};
+
+/// The loaded wave manifest. Exists once `assets/manifest.waves.ron` has
+/// loaded.
+#[derive(Resource, Default)]
+pub struct WaveScripts {
+    pub waves: Vec<WaveScript>,
+    pub loop_from: usize,
+}
+
+impl WaveScripts {
+    /// The wave at `index`. Panics if the manifest has no such wave.
+    pub fn get(&self, index: usize) -> &WaveScript {
+        self.waves
+            .get(index)
+            .unwrap_or_else(|| panic!("no wave {} in {}", index, WAVE_MANIFEST))
+    }
+
+    /// Index of the wave that follows the one at `index`.
+    pub fn next(&self, index: usize) -> usize {
+        match index + 1 < self.waves.len() {
+            true => index + 1,
+            false => self.loop_from.min(self.waves.len() - 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavePhase {
+    /// Waiting out the pause before the next wave.
+    Intermission,
+    Running,
+}
+
+/// Where the match is up to: which wave is on, which of its groups have
+/// come in, and how long until the next one.
+#[derive(Resource, Debug)]
+pub struct WaveDirector {
+    /// The wave being played, counting from 1. 0 before the first.
+    pub number: u32,
+    /// Index into `WaveScripts` of the wave being played, or of the next one
+    /// during an intermission.
+    pub script: usize,
+    pub phase: WavePhase,
+    pub pause: Timer,
+    /// Time since the current wave started.
+    pub elapsed: Duration,
+    /// Which of the current wave's groups have spawned.
+    pub spawned: Vec<bool>,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self {
+            number: 0,
+            script: 0,
+            phase: WavePhase::Intermission,
+            pause: Timer::from_seconds(FIRST_WAVE_DELAY, TimerMode::Once),
+            elapsed: Duration::ZERO,
+            spawned: Vec::new(),
+        }
+    }
+}
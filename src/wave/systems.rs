@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
+use crate::difficulty::resources::Difficulty;
+use crate::enemy::components::Enemy;
+use crate::enemy::resources::EnemyTypes;
+use crate::enemy::utils::spawn_enemy;
+use crate::enemy::ENEMY_MANIFEST;
+use crate::game::events::{WaveCleared, WaveStarted};
+use crate::wave::assets::ClearCondition;
+use crate::wave::components::WaveAnnouncement;
+use crate::wave::resources::{WaveDirector, WavePhase, WaveScripts};
+use crate::wave::utils::formation_positions;
+use crate::wave::{WAVE_ANNOUNCEMENT_TIME, WAVE_MANIFEST};
+
+/// Start each wave once the pause before it is over, bring in its groups as
+/// their delays come up, and end it when its clear condition is met.
+#[allow(clippy::too_many_arguments)]
+pub fn wave_director_system(
+    mut commands: Commands,
+    mut wave_director: ResMut<WaveDirector>,
+    wave_scripts: Res<WaveScripts>,
+    enemy_query: Query<(), With<Enemy>>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    enemy_types: Res<EnemyTypes>,
+    difficulty: Res<Difficulty>,
+    mut game_rng: ResMut<GameRng>,
+    fixed_time: Res<FixedTime>,
+    mut wave_started_events: EventWriter<WaveStarted>,
+    mut wave_cleared_events: EventWriter<WaveCleared>,
+) {
+    let wave_director = &mut *wave_director;
+
+    if wave_director.phase == WavePhase::Intermission {
+        wave_director.pause.tick(fixed_time.period);
+        if !wave_director.pause.finished() {
+            return;
+        }
+
+        wave_director.number += 1;
+        wave_director.phase = WavePhase::Running;
+        wave_director.elapsed = Default::default();
+        wave_director.spawned = vec![false; wave_scripts.get(wave_director.script).groups.len()];
+
+        wave_started_events.send(WaveStarted {
+            number: wave_director.number,
+        });
+    } else {
+        wave_director.elapsed += fixed_time.period;
+    }
+
+    let wave = wave_scripts.get(wave_director.script);
+
+    // Checked before this tick's groups spawn, as they only show up in
+    // `enemy_query` from the next tick.
+    let all_spawned = wave_director.spawned.iter().all(|spawned| *spawned);
+    let cleared = match wave.clear {
+        ClearCondition::NoEnemiesLeft => all_spawned && enemy_query.is_empty(),
+        ClearCondition::Timeout(seconds) => {
+            all_spawned && wave_director.elapsed.as_secs_f32() >= seconds
+        }
+    };
+
+    if cleared {
+        wave_cleared_events.send(WaveCleared {
+            number: wave_director.number,
+        });
+
+        wave_director.phase = WavePhase::Intermission;
+        wave_director.pause =
+            Timer::from_seconds(difficulty.spawn_delay(wave.pause), TimerMode::Once);
+        wave_director.script = wave_scripts.next(wave_director.script);
+        return;
+    }
+
+    let rng = &mut game_rng.enemies;
+
+    for (group, spawned) in wave.groups.iter().zip(wave_director.spawned.iter_mut()) {
+        if *spawned || wave_director.elapsed.as_secs_f32() < difficulty.spawn_delay(group.delay) {
+            continue;
+        }
+        *spawned = true;
+
+        // already warned about by `wave_scripts_check_system`
+        if !enemy_types.types.contains_key(&group.enemy) {
+            continue;
+        }
+
+        let count = difficulty.spawn_count(group.count);
+        let positions = formation_positions(group.formation, group.side, count, &playfield, rng);
+        for position in positions {
+            spawn_enemy(
+                &mut commands,
+                &sprite_registry,
+                &enemy_types,
+                &difficulty,
+                &group.enemy,
+                position,
+                rng,
+            );
+        }
+    }
+}
+
+/// Keep the director on a wave that exists once the wave manifest is
+/// reloaded, and warn about groups of enemies the enemy manifest doesn't
+/// have, which never spawn.
+pub fn wave_scripts_check_system(
+    mut wave_director: ResMut<WaveDirector>,
+    wave_scripts: Res<WaveScripts>,
+    enemy_types: Res<EnemyTypes>,
+) {
+    if wave_scripts.is_changed() {
+        if wave_director.script >= wave_scripts.waves.len() {
+            wave_director.script = wave_scripts.loop_from.min(wave_scripts.waves.len() - 1);
+        }
+        if wave_director.phase == WavePhase::Running {
+            let groups = wave_scripts.get(wave_director.script).groups.len();
+            wave_director.spawned.resize(groups, false);
+        }
+    }
+
+    if !(wave_scripts.is_changed() || enemy_types.is_changed()) {
+        return;
+    }
+    for (index, wave) in wave_scripts.waves.iter().enumerate() {
+        for group in wave.groups.iter() {
+            if !enemy_types.types.contains_key(&group.enemy) {
+                warn!(
+                    "{}: wave {} has a group of \"{}\", which isn't in {}",
+                    WAVE_MANIFEST, index, group.enemy, ENEMY_MANIFEST
+                );
+            }
+        }
+    }
+}
+
+pub fn wave_announcement_spawn_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut wave_started_events: EventReader<WaveStarted>,
+    announcement_query: Query<Entity, With<WaveAnnouncement>>,
+) {
+    for wave_started in wave_started_events.iter() {
+        for announcement_entity in announcement_query.iter() {
+            commands.entity(announcement_entity).despawn_recursive();
+        }
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            top: Val::Percent(30.0),
+                            ..default()
+                        },
+                        size: Size::width(Val::Percent(100.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                WaveAnnouncement {
+                    timer: Timer::from_seconds(WAVE_ANNOUNCEMENT_TIME, TimerMode::Once),
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    format!("Wave {}", wave_started.number),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 80.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+    }
+}
+
+/// Fade the wave banner out over its last half, then remove it.
+pub fn wave_announcement_fade_system(
+    mut commands: Commands,
+    mut announcement_query: Query<(Entity, &mut WaveAnnouncement, &Children)>,
+    mut text_query: Query<&mut Text>,
+    fixed_time: Res<FixedTime>,
+) {
+    for (announcement_entity, mut announcement, children) in announcement_query.iter_mut() {
+        announcement.timer.tick(fixed_time.period);
+
+        if announcement.timer.finished() {
+            commands.entity(announcement_entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = (announcement.timer.percent_left() * 2.0).min(1.0);
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                for section in text.sections.iter_mut() {
+                    section.style.color.set_a(alpha);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::common::resources::Playfield;
+use crate::wave::assets::{Formation, SpawnSide};
+use crate::wave::FORMATION_SPACING;
+
+/// Where each of `count` enemies in a formation starts, just off the right
+/// edge of the playfield.
+pub fn formation_positions(
+    formation: Formation,
+    side: SpawnSide,
+    count: u32,
+    playfield: &Playfield,
+    rng: &mut impl Rng,
+) -> Vec<Vec2> {
+    let x_start = playfield.width / 2.0 + 40.0;
+    let (y_low, y_high) = match side {
+        SpawnSide::Right => (
+            -playfield.height / 2.0 + 50.0,
+            playfield.height / 2.0 - 50.0,
+        ),
+        SpawnSide::Top => (0.0, playfield.height / 2.0 - 50.0),
+        SpawnSide::Bottom => (-playfield.height / 2.0 + 50.0, 0.0),
+    };
+    // too short a playfield to keep clear of the edges, so keep to the middle
+    let (y_low, y_high) = match y_low < y_high {
+        true => (y_low, y_high),
+        false => (0.0, 0.0),
+    };
+
+    match formation {
+        Formation::Scatter => (0..count)
+            .map(|_| {
+                Vec2::new(
+                    rng.gen_range(x_start..x_start + playfield.width * 3.0 / 8.0),
+                    random_between(rng, y_low, y_high),
+                )
+            })
+            .collect(),
+        Formation::Line => (0..count)
+            .map(|index| {
+                let spread = (index as f32 + 0.5) / count as f32;
+                Vec2::new(x_start, y_low + (y_high - y_low) * spread)
+            })
+            .collect(),
+        Formation::Column => {
+            let y = random_between(rng, y_low, y_high);
+            (0..count)
+                .map(|index| Vec2::new(x_start + index as f32 * FORMATION_SPACING, y))
+                .collect()
+        }
+        Formation::Wedge => {
+            let y = random_between(rng, y_low, y_high);
+            (0..count)
+                .map(|index| {
+                    // followers alternate above and below, a rank further back
+                    let rank = index.div_ceil(2) as f32;
+                    let above = if index % 2 == 1 { 1.0 } else { -1.0 };
+                    Vec2::new(
+                        x_start + rank * FORMATION_SPACING,
+                        (y + above * rank * FORMATION_SPACING).clamp(y_low, y_high),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+/// A random point from `low` up to `high`, or `low` when there's no room.
+fn random_between(rng: &mut impl Rng, low: f32, high: f32) -> f32 {
+    match low < high {
+        true => rng.gen_range(low..high),
+        false => low,
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::collision::components::Collider;
+use crate::common::assets::{ManifestResource, RonManifest};
+use crate::weapon::resources::WeaponTypes;
+use crate::weapon::{STARTING_WEAPON, WEAPON_MANIFEST};
+
+/// Every gun a player can carry, keyed by name. Loaded from
+/// `assets/manifest.weapons.ron`.
+#[derive(TypeUuid, Deserialize, Debug, Clone)]
+#[uuid = "c81f0b52-6d3e-4a97-b1e4-2f9a7c5d0e38"]
+pub struct WeaponManifest {
+    pub weapons: BTreeMap<String, WeaponType>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponType {
+    /// Shown when the weapon is picked up.
+    pub name: String,
+    /// Animation of the `bullet` sheet its projectiles are drawn with.
+    pub projectile: String,
+    /// Hitbox of a projectile, in pixels of the `bullet` sheet. The sheet's
+    /// own collider if not given.
+    #[serde(default)]
+    pub collider: Option<Collider>,
+    /// Projectile speed, in the same units as enemy speeds.
+    pub speed: f32,
+    pub damage: u32,
+    /// How hard a projectile pushes back an enemy it doesn't kill.
+    pub knockback: f32,
+    /// Projectiles per shot.
+    #[serde(default = "default_projectiles")]
+    pub projectiles: u32,
+    /// Angle, in degrees, a shot's projectiles are fanned out over.
+    #[serde(default)]
+    pub spread: f32,
+    /// Shots per second, whether tapped or held down.
+    pub fire_rate: f32,
+    /// If set, holding fire also charges a stronger shot, let off when the
+    /// button comes up.
+    #[serde(default)]
+    pub charge: Option<ChargeShot>,
+    /// How many enemies a projectile passes through before it is spent.
+    #[serde(default)]
+    pub pierce: u32,
+    /// How many times a projectile glances off the edges of the playfield
+    /// before it is let go.
+    #[serde(default)]
+    pub bounces: u32,
+    /// How often the weapon turns up as a pickup, relative to the others.
+    /// 0 for weapons that never do.
+    #[serde(default)]
+    pub pickup_weight: f32,
+}
+
+/// What a fully charged shot does differently.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ChargeShot {
+    /// Seconds fire has to be held for.
+    pub time: f32,
+    /// Multiplies the weapon's damage.
+    pub damage_multiplier: u32,
+    /// Multiplies the size of the projectiles, and so their hitboxes.
+    pub scale: f32,
+    /// Enemies the projectiles pass through, if more than the weapon's own.
+    #[serde(default)]
+    pub pierce: u32,
+}
+
+impl WeaponType {
+    /// What, if anything, stops the weapon from being fired.
+    fn check(&self) -> Result<(), String> {
+        // the cooldown between shots is 1 / fire_rate seconds
+        if !self.fire_rate.is_finite() || self.fire_rate <= 0.0 {
+            return Err(format!("has a fire_rate of {}", self.fire_rate));
+        }
+        if self.projectiles == 0 {
+            return Err("fires no projectiles".to_string());
+        }
+        if !self.pickup_weight.is_finite() || self.pickup_weight < 0.0 {
+            return Err(format!("has a pickup_weight of {}", self.pickup_weight));
+        }
+        if let Some(charge) = &self.charge {
+            if !charge.time.is_finite() || charge.time < 0.0 {
+                return Err(format!("takes {} seconds to charge", charge.time));
+            }
+            if !charge.scale.is_finite() || charge.scale <= 0.0 {
+                return Err(format!("scales charged shots by {}", charge.scale));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_projectiles() -> u32 {
+    1
+}
+
+impl RonManifest for WeaponManifest {
+    const PATH: &'static str = WEAPON_MANIFEST;
+    const EXTENSIONS: &'static [&'static str] = &["weapons.ron"];
+}
+
+impl ManifestResource for WeaponManifest {
+    type Resource = WeaponTypes;
+
+    fn build(&self) -> Result<WeaponTypes, String> {
+        if !self.weapons.contains_key(STARTING_WEAPON) {
+            return Err(format!("no \"{}\" weapon to start with", STARTING_WEAPON));
+        }
+        for (key, weapon) in self.weapons.iter() {
+            weapon
+                .check()
+                .map_err(|problem| format!("weapon \"{}\" {}", key, problem))?;
+        }
+
+        Ok(WeaponTypes {
+            types: self.weapons.clone(),
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::weapon::assets::WeaponType;
+
+/// The gun a player carries.
+#[derive(Component, Debug)]
+pub struct Weapon {
+    /// `WeaponTypes` key.
+    pub kind: String,
+    /// Runs out once the weapon's fire rate allows another shot.
+    pub cooldown: Timer,
+    /// How long fire has been held, for weapons with a charge shot.
+    pub charge: Option<Timer>,
+}
+
+impl Weapon {
+    /// A `kind` weapon, ready to fire and with no charge built up.
+    pub fn new(kind: &str, weapon_type: &WeaponType) -> Self {
+        let mut cooldown = Timer::from_seconds(1.0 / weapon_type.fire_rate, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+
+        Self {
+            kind: kind.to_string(),
+            cooldown,
+            charge: weapon_type
+                .charge
+                .map(|charge| Timer::from_seconds(charge.time, TimerMode::Once)),
+        }
+    }
+}
+
+/// A projectile that goes on through enemies it hits.
+#[derive(Component, Debug)]
+pub struct Piercing {
+    /// Enemies still to pass through.
+    pub remaining: u32,
+    /// Enemies already hit, so each is only hit once.
+    pub hits: Vec<Entity>,
+}
+
+/// A projectile that glances off the edges of the playfield.
+#[derive(Component, Debug)]
+pub struct Bouncing {
+    pub remaining: u32,
+}
+
+/// A power-up that swaps the collecting player's weapon.
+#[derive(Component, Debug, Clone)]
+pub struct WeaponPickup {
+    /// `WeaponTypes` key.
+    pub kind: String,
+}
@@ -0,0 +1,50 @@
+pub mod assets;
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::common::ManifestPlugin;
+use crate::game::resources::GameDataLoaded;
+use crate::item::ItemSystemSet;
+use crate::weapon::assets::WeaponManifest;
+use crate::weapon::resources::WeaponSpawnTimer;
+use crate::weapon::systems::{
+    weapon_pickup_spawn_system, weapon_spawn_timer_tick_system, weapon_sprites_check_system,
+};
+
+pub const WEAPON_MANIFEST: &str = "manifest.weapons.ron";
+/// What every player starts out with, and goes back to after losing a life.
+pub const STARTING_WEAPON: &str = "single";
+/// Animation of the `bullet` sheet the starting weapon falls back to if the
+/// sheet can't draw its own projectile. Every sprite manifest has it.
+pub const FALLBACK_PROJECTILE: &str = "fireball";
+pub const WEAPON_SPAWN_TIME: f32 = 15.0;
+/// Weapon pickups are drawn with their projectile, blown up and tinted.
+pub const WEAPON_PICKUP_SCALE: f32 = 5.0;
+pub const WEAPON_PICKUP_COLOR: Color = Color::rgb(1.0, 0.9, 0.4);
+/// In pixels of the `bullet` sheet.
+pub const WEAPON_PICKUP_RADIUS: f32 = 5.0;
+/// Players glow while they have a charge shot ready.
+pub const CHARGED_COLOR: Color = Color::rgb(1.0, 1.0, 0.5);
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeaponSpawnTimer>()
+            .add_plugin(ManifestPlugin::<WeaponManifest>::default())
+            .add_system(
+                weapon_sprites_check_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .run_if(resource_exists::<GameDataLoaded>()),
+            )
+            .add_systems(
+                (weapon_pickup_spawn_system, weapon_spawn_timer_tick_system)
+                    .chain()
+                    .in_set(ItemSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
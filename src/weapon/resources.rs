@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::weapon::assets::WeaponType;
+use crate::weapon::{WEAPON_MANIFEST, WEAPON_SPAWN_TIME};
+
+/// The loaded weapon manifest. Exists once `assets/manifest.weapons.ron`
+/// has loaded.
+#[derive(Resource, Default)]
+pub struct WeaponTypes {
+    pub types: BTreeMap<String, WeaponType>,
+}
+
+impl WeaponTypes {
+    /// The weapon type named `key`. Panics if the manifest has no such type.
+    pub fn get(&self, key: &str) -> &WeaponType {
+        self.types
+            .get(key)
+            .unwrap_or_else(|| panic!("no weapon named \"{}\" in {}", key, WEAPON_MANIFEST))
+    }
+
+    /// Pick a weapon for a pickup at random, weighted by `pickup_weight`.
+    /// `None` if no weapon turns up as a pickup.
+    pub fn choose_pickup(&self, rng: &mut impl Rng) -> Option<&str> {
+        let total_weight: f32 = self.types.values().map(|weapon| weapon.pickup_weight).sum();
+        if total_weight <= 0.0 {
+            return None;
+        }
+        let mut pick = rng.gen_range(0.0..total_weight);
+
+        for (key, weapon) in self.types.iter() {
+            if pick < weapon.pickup_weight {
+                return Some(key);
+            }
+            pick -= weapon.pickup_weight;
+        }
+
+        self.types
+            .iter()
+            .rev()
+            .find(|(_, weapon)| weapon.pickup_weight > 0.0)
+            .map(|(key, _)| key.as_str())
+    }
+}
+
+#[derive(Resource)]
+pub struct WeaponSpawnTimer {
+    pub timer: Timer,
+}
+
+impl Default for WeaponSpawnTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(WEAPON_SPAWN_TIME, TimerMode::Repeating),
+        }
+    }
+}
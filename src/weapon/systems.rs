@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::animation::components::AnimationController;
+use crate::collision::components::Collider;
+use crate::common::components::{Movable, Velocity};
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
+use crate::item::bundles::PowerUpBundle;
+use crate::item::components::{ItemVariant, PowerUp};
+use crate::item::utils::random_spawn_position;
+use crate::weapon::components::WeaponPickup;
+use crate::weapon::resources::{WeaponSpawnTimer, WeaponTypes};
+use crate::weapon::{
+    FALLBACK_PROJECTILE, STARTING_WEAPON, WEAPON_MANIFEST, WEAPON_PICKUP_COLOR,
+    WEAPON_PICKUP_RADIUS, WEAPON_PICKUP_SCALE,
+};
+
+/// Leave out weapons whose projectiles the `bullet` sheet can't draw, once
+/// either manifest is loaded. The starting weapon can't be left out, so it
+/// is drawn with `FALLBACK_PROJECTILE` instead. Runs before any tick, so
+/// nothing tries to fire them.
+pub fn weapon_sprites_check_system(
+    mut weapon_types: ResMut<WeaponTypes>,
+    sprite_registry: Res<SpriteRegistry>,
+) {
+    if !(weapon_types.is_changed() || sprite_registry.is_changed()) {
+        return;
+    }
+
+    let undrawable: Vec<(String, String)> = weapon_types
+        .types
+        .iter()
+        .filter_map(|(key, weapon_type)| {
+            let problem = sprite_registry
+                .check("bullet", &[&weapon_type.projectile])
+                .err()?;
+            Some((key.clone(), problem))
+        })
+        .collect();
+    for (key, problem) in undrawable.iter() {
+        if key == STARTING_WEAPON {
+            warn!(
+                "{}: weapon \"{}\" {}, drawing it with \"{}\"",
+                WEAPON_MANIFEST, key, problem, FALLBACK_PROJECTILE
+            );
+            weapon_types.types.get_mut(key).unwrap().projectile = FALLBACK_PROJECTILE.to_string();
+        } else {
+            warn!(
+                "{}: weapon \"{}\" {}, leaving it out",
+                WEAPON_MANIFEST, key, problem
+            );
+            weapon_types.types.remove(key);
+        }
+    }
+}
+
+/// Drop a random weapon somewhere on the playfield every so often, unless
+/// one is already waiting to be picked up.
+pub fn weapon_pickup_spawn_system(
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+    weapon_spawn_timer: Res<WeaponSpawnTimer>,
+    pickup_query: Query<(), (With<PowerUp>, With<WeaponPickup>)>,
+    sprite_registry: Res<SpriteRegistry>,
+    weapon_types: Res<WeaponTypes>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !weapon_spawn_timer.timer.finished() || !pickup_query.is_empty() {
+        return;
+    }
+
+    let rng = &mut game_rng.items;
+
+    let Some(weapon_key) = weapon_types.choose_pickup(rng) else {
+        return;
+    };
+    let weapon_type = weapon_types.get(weapon_key);
+
+    let translation = random_spawn_position(&playfield, rng);
+
+    let bullet_sprite = sprite_registry.get("bullet");
+    let projectile_clip = bullet_sprite.clip(&weapon_type.projectile);
+
+    commands.spawn((
+        PowerUpBundle {
+            power_up: PowerUp,
+            variant: ItemVariant::Weapon,
+            collider: Collider::circle(WEAPON_PICKUP_RADIUS),
+            animation: AnimationController::new("idle", projectile_clip.clone()),
+            movable: Movable::default(),
+            velocity: Velocity::default(),
+            sprite_sheet: SpriteSheetBundle {
+                texture_atlas: bullet_sprite.atlas(),
+                sprite: TextureAtlasSprite {
+                    index: projectile_clip.first,
+                    color: WEAPON_PICKUP_COLOR,
+                    ..Default::default()
+                },
+                transform: Transform {
+                    translation,
+                    scale: Vec3::splat(WEAPON_PICKUP_SCALE),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        WeaponPickup {
+            kind: weapon_key.to_string(),
+        },
+    ));
+}
+
+pub fn weapon_spawn_timer_tick_system(
+    mut weapon_spawn_timer: ResMut<WeaponSpawnTimer>,
+    fixed_time: Res<FixedTime>,
+) {
+    weapon_spawn_timer.timer.tick(fixed_time.period);
+}
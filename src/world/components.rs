@@ -1,4 +1,16 @@
 use bevy::prelude::Component;
 
+/// A decoration of one of the `PARALLAX_LAYERS`.
 #[derive(Component)]
-pub struct Tree;
+pub struct ParallaxSprite {
+    /// Index into `PARALLAX_LAYERS`.
+    pub layer: usize,
+}
+
+/// A strip of ground tiles, scrolled as one and replaced by a fresh one once
+/// it has gone off the left of the playfield.
+#[derive(Component)]
+pub struct GroundChunk {
+    /// In pixels, so where the next chunk has to start.
+    pub width: f32,
+}
@@ -1,21 +1,73 @@
 pub mod components;
-pub mod resources;
 pub mod systems;
 
+use crate::common::{TickSet, BASE_SPEED, SCROLL_X_VELOCITY, TIME_STEP};
+use crate::game::resources::GameDataLoaded;
 use crate::game::states::GameState;
 use crate::world::systems::{
-    tree_movement_system, tree_spawn_system, tree_spawn_system_init, tree_spawn_timer_tick_system,
-    trees_spawn_over_time_system,
+    ground_movement_system, ground_spawn_system_init, parallax_movement_system,
+    parallax_spawn_system_init,
 };
 use bevy::prelude::*;
 
-use self::resources::TreeSpawnTimer;
+/// Pixels the ground scrolls left each tick. Anything lying on it scrolls
+/// along at the same pace.
+pub const GROUND_SCROLL_STEP: f32 = SCROLL_X_VELOCITY * TIME_STEP * BASE_SPEED / 2.0 + 1.0;
+/// Columns of tiles in each ground chunk.
+pub const GROUND_CHUNK_COLUMNS: usize = 8;
+/// Odds of a ground tile having a grass tuft on it.
+pub const GROUND_TUFT_CHANCE: f64 = 0.2;
+/// Ground tiles are shaded anywhere from this to full brightness.
+pub const GROUND_SHADE_MIN: f32 = 0.9;
+/// Behind everything else. The 2D camera sees no further back than -0.1.
+pub const GROUND_Z: f32 = -0.05;
 
-pub const TREE_SPAWN_TIME: f32 = 5.0;
-pub const NUMBER_OF_TREES: u8 = 4;
-pub const TREE_SPRITE: &str = "Bush-001.png";
-pub const TREE_SIZE: (f32, f32) = (32.0, 16.0); // (x, y)
-pub const TREE_SCALE: f32 = 0.5;
+/// A band of decorations scrolled across the playfield at its own pace, to
+/// give the world some depth.
+pub struct ParallaxLayer {
+    /// `SpriteRegistry` key of the sheet its sprites are drawn with.
+    pub sprite: &'static str,
+    /// Multiplies the sheet's own scale.
+    pub scale: f32,
+    pub color: Color,
+    pub z: f32,
+    /// Multiplies `GROUND_SCROLL_STEP`: under 1 looks further away than the
+    /// ground, over 1 nearer.
+    pub scroll_factor: f32,
+    /// Sprites per 1000 pixels of playfield width.
+    pub density: f32,
+}
+
+/// From the back to the front.
+pub const PARALLAX_LAYERS: [ParallaxLayer; 3] = [
+    // far trees
+    ParallaxLayer {
+        sprite: "tree",
+        scale: 0.7,
+        color: Color::rgb(0.6, 0.65, 0.6),
+        z: 0.1,
+        scroll_factor: 0.6,
+        density: 12.0,
+    },
+    // mid bushes
+    ParallaxLayer {
+        sprite: "tree",
+        scale: 1.0,
+        color: Color::WHITE,
+        z: 0.2,
+        scroll_factor: 1.0,
+        density: 10.0,
+    },
+    // near grass tufts, over everything but the HUD
+    ParallaxLayer {
+        sprite: "grass_tuft",
+        scale: 1.2,
+        color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+        z: 15.0,
+        scroll_factor: 1.4,
+        density: 3.0,
+    },
+];
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct WorldSystemSet;
@@ -24,18 +76,26 @@ pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<TreeSpawnTimer>()
-            .add_state::<GameState>()
-            .configure_set(WorldSystemSet.run_if(in_state(GameState::Playing)))
-            .add_startup_system(tree_spawn_system_init)
-            .add_startup_system(tree_spawn_system)
+        app.add_state::<GameState>()
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                schedule.configure_set(
+                    WorldSystemSet
+                        .run_if(in_state(GameState::Playing))
+                        .after(TickSet::Begin)
+                        .before(TickSet::End),
+                );
+            })
+            .add_systems(
+                (ground_spawn_system_init, parallax_spawn_system_init)
+                    .chain()
+                    .in_base_set(CoreSet::PreUpdate)
+                    .distributive_run_if(resource_added::<GameDataLoaded>()),
+            )
             .add_systems(
-                (
-                    tree_movement_system,
-                    tree_spawn_timer_tick_system,
-                    trees_spawn_over_time_system,
-                )
-                    .in_set(WorldSystemSet),
+                (ground_movement_system, parallax_movement_system)
+                    .chain()
+                    .in_set(WorldSystemSet)
+                    .in_schedule(CoreSchedule::FixedUpdate),
             );
     }
 }
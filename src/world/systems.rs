@@ -1,131 +1,258 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use rand::Rng;
 
-use crate::common::components::{Movable, Velocity};
-use crate::common::{BASE_SPEED, SCROLL_X_VELOCITY, SCROLL_Y_VELOCITY, TIME_STEP};
-use crate::world::components::Tree;
-use crate::world::resources::TreeSpawnTimer;
-use crate::world::{NUMBER_OF_TREES, TREE_SCALE, TREE_SPRITE};
+use crate::common::components::Interpolated;
+use crate::common::resources::{GameRng, Playfield, SpriteRegistry};
+use crate::world::components::{GroundChunk, ParallaxSprite};
+use crate::world::{
+    ParallaxLayer, GROUND_CHUNK_COLUMNS, GROUND_SCROLL_STEP, GROUND_SHADE_MIN, GROUND_TUFT_CHANCE,
+    GROUND_Z, PARALLAX_LAYERS,
+};
 
-pub fn tree_spawn_system_init(
+/// Lay ground chunks across the whole playfield, so there is no bare
+/// background to begin with.
+pub fn ground_spawn_system_init(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
-
-    let (spawn_area_width_start, spawn_area_width_end) =
-        (-window.width() + 5.0, window.width() / 2.0 - 5.0);
-    let (spawn_area_height_start, spawn_area_height_end) =
-        (-window.height() + 5.0, window.height() / 2.0 - 5.0);
-
-    // println!("spawn_area_width_start: {}", spawn_area_width_start);
-    // println!("spawn_area_width_end: {}", spawn_area_width_end);
-
-    for _ in 0..40 {
-        let mut rng = rand::thread_rng();
-        let random_width = rng.gen_range(spawn_area_width_start..spawn_area_width_end);
-        let random_height = rng.gen_range(spawn_area_height_start..spawn_area_height_end);
-        // println!("window_width {}", window.width());
-        // println!("window_height {}", window.height());
-        // println!("random_width {random_width}");sdwwvj
-        // println!("random_height {random_height}");
-        commands.spawn((
-            SpriteBundle {
-                texture: asset_server.load(TREE_SPRITE),
-                transform: Transform {
-                    translation: Vec3::new(random_width, random_height, 0.0),
-                    scale: Vec3::new(TREE_SCALE, TREE_SCALE, 1.0),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            Tree {},
-            Movable { auto_despawn: true },
-            Velocity {
-                x: SCROLL_X_VELOCITY,
-                y: SCROLL_Y_VELOCITY,
-            },
-        ));
+    let mut left = -playfield.width / 2.0;
+    while left < playfield.width / 2.0 {
+        left += ground_chunk_spawn(
+            &mut commands,
+            &playfield,
+            &sprite_registry,
+            &mut game_rng.ground,
+            left,
+        );
     }
 }
 
-pub fn tree_spawn_system(
+/// Scroll the ground along, dropping chunks that
+/// have gone off the left of the playfield and laying new ones on the right
+/// before the ground runs out.
+pub fn ground_movement_system(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    mut chunk_query: Query<(Entity, &GroundChunk, &mut Transform)>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
-
-    let (spawn_area_width_start, spawn_area_width_end) =
-        (window.width() / 2.0, window.width() - window.width() / 8.0);
-    let (spawn_area_height_start, spawn_area_height_end) =
-        (-window.height() / 2.0 + 50.0, window.height() / 2.0 - 50.0);
-
-    // println!("spawn_area_width_start: {}", spawn_area_width_start);
-    // println!("spawn_area_width_end: {}", spawn_area_width_end);
-
-    for _ in 0..NUMBER_OF_TREES {
-        let mut rng = rand::thread_rng();
-        let random_width = rng.gen_range(spawn_area_width_start..spawn_area_width_end);
-        let random_height = rng.gen_range(spawn_area_height_start..spawn_area_height_end);
-        // println!("window_width {}", window.width());
-        // println!("window_height {}", window.height());
-        // println!("random_width {random_width}");
-        // println!("random_height {random_height}");
-        commands.spawn((
-            SpriteBundle {
-                texture: asset_server.load(TREE_SPRITE),
-                transform: Transform {
-                    translation: Vec3::new(random_width, random_height, 0.0),
-                    scale: Vec3::new(TREE_SCALE, TREE_SCALE, 1.0),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            Tree {},
-            Movable { auto_despawn: true },
-            Velocity {
-                x: SCROLL_X_VELOCITY,
-                y: SCROLL_Y_VELOCITY,
-            },
-        ));
+    let mut right = -playfield.width / 2.0;
+
+    for (chunk_entity, chunk, mut chunk_transform) in chunk_query.iter_mut() {
+        let chunk_translation = &mut chunk_transform.translation;
+        chunk_translation.x -= GROUND_SCROLL_STEP;
+
+        if chunk_translation.x + chunk.width / 2.0 < -playfield.width / 2.0 {
+            commands.entity(chunk_entity).despawn_recursive();
+        } else {
+            right = right.max(chunk_translation.x + chunk.width / 2.0);
+        }
+    }
+
+    while right < playfield.width / 2.0 {
+        right += ground_chunk_spawn(
+            &mut commands,
+            &playfield,
+            &sprite_registry,
+            &mut game_rng.ground,
+            right,
+        );
     }
 }
 
-pub fn tree_spawn_timer_tick_system(mut tree_spawn_timer: ResMut<TreeSpawnTimer>, time: Res<Time>) {
-    tree_spawn_timer.timer.tick(time.delta());
+/// Spawn a chunk of ground tiles tall enough to cover the playfield, with
+/// its left edge at `left`. Each tile is flipped and shaded at random, and
+/// some get a grass tuft, so the ground doesn't repeat. Returns the chunk's
+/// width.
+fn ground_chunk_spawn(
+    commands: &mut Commands,
+    playfield: &Playfield,
+    sprite_registry: &SpriteRegistry,
+    rng: &mut impl Rng,
+    left: f32,
+) -> f32 {
+    let grass_sprite = sprite_registry.get("grass");
+    let tuft_sprite = sprite_registry.get("grass_tuft");
+
+    let tile_size = grass_sprite.sheet.width * grass_sprite.sheet.scale;
+    let rows = (playfield.height / tile_size).ceil() as usize + 1;
+    let width = tile_size * GROUND_CHUNK_COLUMNS as f32;
+    let height = tile_size * rows as f32;
+
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(left + width / 2.0, 0.0, GROUND_Z)),
+            GroundChunk { width },
+            Interpolated::default(),
+        ))
+        .with_children(|parent| {
+            for row in 0..rows {
+                for column in 0..GROUND_CHUNK_COLUMNS {
+                    let x = (column as f32 + 0.5) * tile_size - width / 2.0;
+                    let y = (row as f32 + 0.5) * tile_size - height / 2.0;
+                    let shade = rng.gen_range(GROUND_SHADE_MIN..=1.0);
+
+                    parent.spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgb(shade, shade, shade),
+                            flip_x: rng.gen_bool(0.5),
+                            flip_y: rng.gen_bool(0.5),
+                            ..Default::default()
+                        },
+                        texture: grass_sprite.image.clone(),
+                        transform: Transform {
+                            translation: Vec3::new(x, y, 0.0),
+                            scale: Vec3::new(
+                                grass_sprite.sheet.scale,
+                                grass_sprite.sheet.scale,
+                                1.0,
+                            ),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+
+                    if rng.gen_bool(GROUND_TUFT_CHANCE) {
+                        let offset = rng.gen_range(-tile_size / 4.0..tile_size / 4.0);
+                        parent.spawn(SpriteBundle {
+                            sprite: Sprite {
+                                flip_x: rng.gen_bool(0.5),
+                                ..Default::default()
+                            },
+                            texture: tuft_sprite.image.clone(),
+                            transform: Transform {
+                                translation: Vec3::new(x + offset, y, 0.01),
+                                scale: Vec3::new(
+                                    tuft_sprite.sheet.scale,
+                                    tuft_sprite.sheet.scale,
+                                    1.0,
+                                ),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+        });
+
+    width
 }
 
-pub fn trees_spawn_over_time_system(
-    commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-    tree_spawn_timer: Res<TreeSpawnTimer>,
+/// Scatter every parallax layer's decorations across the playfield.
+pub fn parallax_spawn_system_init(
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    if tree_spawn_timer.timer.finished() {
-        tree_spawn_system(commands, window_query, asset_server)
+    let rng = &mut game_rng.world;
+
+    for (index, layer) in PARALLAX_LAYERS.iter().enumerate() {
+        let margin = parallax_margin(&sprite_registry, layer);
+        let band = playfield.width / 2.0 + margin;
+
+        for _ in 0..parallax_count(&playfield, margin, layer) {
+            let x = rng.gen_range(-band..band);
+            parallax_sprite_spawn(&mut commands, &playfield, &sprite_registry, rng, index, x);
+        }
     }
 }
 
-pub fn tree_movement_system(
+/// Scroll each parallax layer at its own pace. Decorations that go off the
+/// left come round again on the right, somewhere else up or down, unless
+/// the playfield has shrunk and the layer has more than it needs; a layer
+/// with too few for the playfield gets more.
+pub fn parallax_movement_system(
     mut commands: Commands,
-    mut tree_query: Query<(Entity, &Velocity, &mut Transform, &Movable), With<Tree>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut sprite_query: Query<(Entity, &ParallaxSprite, &mut Transform, &mut Interpolated)>,
+    playfield: Res<Playfield>,
+    sprite_registry: Res<SpriteRegistry>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
+    let rng = &mut game_rng.world;
 
-    for (tree_entity, velocity, mut tree_transform, movable) in tree_query.iter_mut() {
-        let tree_translation = &mut tree_transform.translation;
-        tree_translation.x -= velocity.x * TIME_STEP * BASE_SPEED / 2.0 + 1.0;
+    let mut counts = [0; PARALLAX_LAYERS.len()];
+    for (_, sprite, _, _) in sprite_query.iter() {
+        counts[sprite.layer] += 1;
+    }
 
-        if movable.auto_despawn {
-            // despawn when out of screen
-            let window_margin = -window.width() / 2.0 - 20.0;
-            if tree_translation.x < window_margin {
-                commands.entity(tree_entity).despawn();
-            }
+    for (entity, sprite, mut transform, mut interpolated) in sprite_query.iter_mut() {
+        let layer = &PARALLAX_LAYERS[sprite.layer];
+        let margin = parallax_margin(&sprite_registry, layer);
+        let band = playfield.width / 2.0 + margin;
+
+        transform.translation.x -= GROUND_SCROLL_STEP * layer.scroll_factor;
+        if transform.translation.x >= -band {
+            continue;
+        }
+
+        if counts[sprite.layer] > parallax_count(&playfield, margin, layer) {
+            commands.entity(entity).despawn();
+            counts[sprite.layer] -= 1;
+        } else {
+            transform.translation.x += 2.0 * band;
+            transform.translation.y =
+                rng.gen_range(-playfield.height / 2.0..playfield.height / 2.0);
+            // or it would be drawn sliding across the whole playfield
+            *interpolated = Interpolated::default();
         }
     }
+
+    for (index, layer) in PARALLAX_LAYERS.iter().enumerate() {
+        let margin = parallax_margin(&sprite_registry, layer);
+        for _ in counts[index]..parallax_count(&playfield, margin, layer) {
+            let x = playfield.width / 2.0 + rng.gen_range(0.0..margin);
+            parallax_sprite_spawn(&mut commands, &playfield, &sprite_registry, rng, index, x);
+        }
+    }
+}
+
+/// How far past the edges of the playfield a layer's decorations go, so
+/// they come and go out of sight.
+fn parallax_margin(sprite_registry: &SpriteRegistry, layer: &ParallaxLayer) -> f32 {
+    let sheet = &sprite_registry.get(layer.sprite).sheet;
+    sheet.width * sheet.scale * layer.scale
+}
+
+/// How many decorations `layer` needs to cover the playfield and the
+/// `margin` either side of it.
+fn parallax_count(playfield: &Playfield, margin: f32, layer: &ParallaxLayer) -> usize {
+    (layer.density * (playfield.width + 2.0 * margin) / 1000.0).round() as usize
+}
+
+fn parallax_sprite_spawn(
+    commands: &mut Commands,
+    playfield: &Playfield,
+    sprite_registry: &SpriteRegistry,
+    rng: &mut impl Rng,
+    layer_index: usize,
+    x: f32,
+) {
+    let layer = &PARALLAX_LAYERS[layer_index];
+    let layer_sprite = sprite_registry.get(layer.sprite);
+    let scale = layer_sprite.sheet.scale * layer.scale;
+    let y = rng.gen_range(-playfield.height / 2.0..playfield.height / 2.0);
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: layer.color,
+                flip_x: rng.gen_bool(0.5),
+                ..Default::default()
+            },
+            texture: layer_sprite.image.clone(),
+            transform: Transform {
+                translation: Vec3::new(x, y, layer.z),
+                scale: Vec3::new(scale, scale, 1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ParallaxSprite { layer: layer_index },
+        Interpolated::default(),
+    ));
 }
@@ -0,0 +1,2019 @@
+use bevy::app::AppExit;
+use bevy::ecs::system::CommandQueue;
+use std::f32::consts::PI;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use bevy_minimal::animation::components::AnimationController;
+use bevy_minimal::app::HeadlessFrameTime;
+use bevy_minimal::collision::components::Collider;
+use bevy_minimal::collision::events::{EnemyHitPlayer, FireballHitEnemy};
+use bevy_minimal::collision::resources::SpatialGrid;
+use bevy_minimal::collision::utils::{colliding, ColliderBounds};
+use bevy_minimal::collision::SPATIAL_GRID_CELL_SIZE;
+use bevy_minimal::common::assets::{ManifestResource, SpriteManifest};
+use bevy_minimal::common::components::{Damage, Health, Interpolated, Velocity, Vitality};
+use bevy_minimal::common::resources::{GameRng, ManifestHandle, Playfield, SpriteRegistry};
+use bevy_minimal::common::TIME_STEP;
+use bevy_minimal::difficulty::assets::DifficultyManifest;
+use bevy_minimal::difficulty::resources::{Difficulty, DifficultyPreset};
+use bevy_minimal::enemy::assets::EnemyManifest;
+use bevy_minimal::enemy::boss::components::{Boss, BossHud};
+use bevy_minimal::enemy::boss::resources::BossEncounter;
+use bevy_minimal::enemy::components::{
+    Bone, ChargeState, Charger, Enemy, EnemyDead, EnemyVariant, Hurt, Shambler, Skirmisher,
+};
+use bevy_minimal::enemy::resources::EnemyTypes;
+use bevy_minimal::enemy::utils::spawn_enemy;
+use bevy_minimal::enemy::DIAMOND_SCORE_MULTIPLIER;
+use bevy_minimal::game::components::InvincibleText;
+use bevy_minimal::game::events::{EnemyKilled, KillCause};
+use bevy_minimal::game::resources::{GameDataLoaded, GameMode};
+use bevy_minimal::game::states::GameState;
+use bevy_minimal::item::assets::Effect;
+use bevy_minimal::item::assets::ItemManifest;
+use bevy_minimal::item::components::{ActiveEffects, ItemVariant, LootDrop, PowerUp};
+use bevy_minimal::item::resources::{ItemSpawnTimer, ItemTypes};
+use bevy_minimal::item::MAX_EFFECT_DURATION;
+use bevy_minimal::player::components::{
+    Fireball, Lives, Player, PlayerInput, PlayerVariant, Score,
+};
+use bevy_minimal::score::resources::{HighScores, Initials, InitialsEntry};
+use bevy_minimal::vfx::components::Explosion;
+use bevy_minimal::wave::assets::{Formation, SpawnSide, WaveManifest};
+use bevy_minimal::wave::components::WaveAnnouncement;
+use bevy_minimal::wave::resources::{WaveDirector, WavePhase};
+use bevy_minimal::wave::utils::formation_positions;
+use bevy_minimal::weapon::assets::WeaponManifest;
+use bevy_minimal::weapon::components::{Bouncing, Weapon, WeaponPickup};
+use bevy_minimal::weapon::resources::WeaponTypes;
+use bevy_minimal::weapon::{CHARGED_COLOR, FALLBACK_PROJECTILE, STARTING_WEAPON};
+use bevy_minimal::world::components::{GroundChunk, ParallaxSprite};
+use bevy_minimal::world::PARALLAX_LAYERS;
+use bevy_minimal::GameAppBuilder;
+
+/// Step the app until the sprite and enemy manifests have loaded. Asset
+/// loading happens on another thread, so how many updates this takes varies
+/// from run to run.
+fn wait_for_game_data(app: &mut App) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !app.world.contains_resource::<GameDataLoaded>() {
+        assert!(Instant::now() < deadline, "game data never loaded");
+        thread::sleep(Duration::from_millis(1));
+        app.update();
+    }
+}
+
+/// A headless solo game, stepped until its game data has loaded.
+fn playing_app() -> App {
+    playing_app_with(|builder| builder)
+}
+
+/// `playing_app`, with more set on the builder first.
+fn playing_app_with(configure: impl FnOnce(GameAppBuilder) -> GameAppBuilder) -> App {
+    let builder = GameAppBuilder::new()
+        .headless(1280.0, 720.0)
+        .game_mode(GameMode::Solo)
+        .start_playing();
+    let mut app = configure(builder).build();
+    wait_for_game_data(&mut app);
+    app
+}
+
+/// Put a pickup of `variant` down on top of `player`, to be picked up on
+/// the next update.
+fn drop_on_player(app: &mut App, player: Entity, variant: ItemVariant) -> Entity {
+    let translation = app.world.get::<Transform>(player).unwrap().translation;
+    app.world
+        .spawn((
+            PowerUp,
+            variant,
+            Collider::circle(5.0),
+            SpriteSheetBundle {
+                transform: Transform::from_translation(translation).with_scale(Vec3::splat(5.0)),
+                ..Default::default()
+            },
+        ))
+        .id()
+}
+
+/// Spawn a `key` enemy at `position`, behaviour and all, as a wave would at
+/// the start of a match.
+fn spawn_enemy_at(app: &mut App, key: &str, position: Vec2) -> Entity {
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &app.world);
+    let enemy = spawn_enemy(
+        &mut commands,
+        app.world.resource::<SpriteRegistry>(),
+        app.world.resource::<EnemyTypes>(),
+        &Difficulty::default(),
+        key,
+        position,
+        &mut ChaCha8Rng::seed_from_u64(0),
+    );
+    command_queue.apply(&mut app.world);
+    enemy
+}
+
+#[test]
+fn headless_game_steps_frames() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(1));
+
+    // player one holds fire the whole time, player two never fires
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(KeyCode::B),
+        state: ButtonState::Pressed,
+    });
+    for _ in 0..300 {
+        app.update();
+    }
+
+    let elapsed = app.world.resource::<Difficulty>().elapsed;
+    assert!((elapsed.as_secs_f32() - 300.0 * TIME_STEP).abs() <= 2.0 * TIME_STEP);
+    assert_eq!(app.world.resource::<WaveDirector>().number, 1);
+
+    // only player one's shots are out there, and they have hit something
+    let mut fireball_query = app.world.query_filtered::<&PlayerVariant, With<Fireball>>();
+    let shooters: Vec<_> = fireball_query.iter(&app.world).collect();
+    assert!(!shooters.is_empty());
+    assert!(shooters
+        .iter()
+        .all(|shooter| **shooter == PlayerVariant::One));
+    let mut corpse_query = app.world.query_filtered::<(), With<EnemyDead>>();
+    assert!(corpse_query.iter(&app.world).count() > 0);
+
+    let mut player_query = app
+        .world
+        .query_filtered::<(&PlayerVariant, &Score), With<Player>>();
+    let scores: Vec<_> = player_query
+        .iter(&app.world)
+        .map(|(player_variant, score)| (player_variant.clone(), score.value))
+        .collect();
+    assert_eq!(scores.len(), 2);
+    for (player_variant, score) in scores {
+        match player_variant {
+            PlayerVariant::One => assert!(score > 0),
+            PlayerVariant::Two => assert_eq!(score, 0),
+        }
+    }
+}
+
+fn enemy_layout(seed: u64) -> Vec<Vec3> {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(seed));
+
+    for _ in 0..120 {
+        app.update();
+    }
+
+    enemy_positions(&mut app)
+}
+
+fn enemy_positions(app: &mut App) -> Vec<Vec3> {
+    let mut enemy_query = app.world.query_filtered::<&Transform, With<Enemy>>();
+    enemy_query
+        .iter(&app.world)
+        .map(|transform| transform.translation)
+        .collect()
+}
+
+#[test]
+fn same_seed_same_enemy_layout() {
+    let layout = enemy_layout(42);
+
+    assert!(!layout.is_empty());
+    assert_eq!(layout, enemy_layout(42));
+    assert_ne!(layout, enemy_layout(7));
+}
+
+/// Where every interpolated entity was left by the last tick, whatever is
+/// being drawn in between.
+fn simulated_positions(app: &mut App) -> Vec<Vec3> {
+    let mut interpolated_query = app.world.query::<&Interpolated>();
+    interpolated_query
+        .iter(&app.world)
+        .filter_map(|interpolated| interpolated.current)
+        .collect()
+}
+
+#[test]
+fn frame_rate_does_not_change_the_simulation() {
+    let step = Duration::from_secs_f32(TIME_STEP);
+    // each run of frames adds up to a whole number of ticks: 2, 1, 1 and 3
+    let frame_times = [
+        2 * step,
+        Duration::ZERO,
+        step / 3,
+        step - step / 3,
+        step / 2,
+        step - step / 2,
+        3 * step,
+        Duration::ZERO,
+        Duration::ZERO,
+    ];
+    let cycles = 17;
+
+    let mut steady = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(11));
+    for _ in 0..7 * cycles {
+        steady.update();
+    }
+
+    let mut uneven = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(11));
+    for _ in 0..cycles {
+        for frame_time in frame_times {
+            uneven.insert_resource(HeadlessFrameTime(frame_time));
+            uneven.update();
+        }
+    }
+
+    assert_eq!(
+        steady.world.resource::<Difficulty>().elapsed,
+        uneven.world.resource::<Difficulty>().elapsed
+    );
+    let positions = simulated_positions(&mut steady);
+    assert!(!enemy_positions(&mut steady).is_empty());
+    assert_eq!(positions, simulated_positions(&mut uneven));
+}
+
+#[test]
+fn moving_things_are_drawn_between_ticks() {
+    let mut app = playing_app();
+    let enemy = spawn_enemy_at(&mut app, "zombie", Vec2::new(300.0, 0.0));
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let step = Duration::from_secs_f32(TIME_STEP);
+    app.insert_resource(HeadlessFrameTime(step / 4));
+    app.update();
+    let interpolated = app.world.get::<Interpolated>(enemy).unwrap();
+    let (previous, current) = (interpolated.previous, interpolated.current.unwrap());
+    assert_ne!(previous, current);
+    let drawn = app.world.get::<Transform>(enemy).unwrap().translation;
+    assert!(drawn.distance(previous.lerp(current, 0.25)) < 1e-3);
+
+    // another quarter step takes no tick either, it only draws further along
+    app.update();
+    let interpolated = app.world.get::<Interpolated>(enemy).unwrap();
+    assert_eq!(interpolated.current, Some(current));
+    let drawn = app.world.get::<Transform>(enemy).unwrap().translation;
+    assert!(drawn.distance(previous.lerp(current, 0.5)) < 1e-3);
+}
+
+fn player_snapshot(app: &mut App) -> Vec<(Vec3, u32)> {
+    let mut player_query = app
+        .world
+        .query_filtered::<(&PlayerVariant, &Transform, &Score), With<Player>>();
+    let mut players: Vec<_> = player_query
+        .iter(&app.world)
+        .map(|(player_variant, transform, score)| {
+            (
+                *player_variant == PlayerVariant::Two,
+                transform.translation,
+                score.value,
+            )
+        })
+        .collect();
+    players.sort_by_key(|(player_two, _, _)| *player_two);
+    players
+        .into_iter()
+        .map(|(_, translation, score)| (translation, score))
+        .collect()
+}
+
+#[test]
+fn replay_reproduces_recorded_run() {
+    let path = std::env::temp_dir().join(format!("bevy-minimal-{}.replay", std::process::id()));
+
+    let mut app = GameAppBuilder::new()
+        .headless(1280.0, 720.0)
+        .seed(3)
+        .start_playing()
+        .record(&path)
+        .build();
+    wait_for_game_data(&mut app);
+
+    for frame in 0..180 {
+        let mut keyboard = app.world.resource_mut::<Input<KeyCode>>();
+        keyboard.release_all();
+        if frame < 90 {
+            keyboard.press(KeyCode::D);
+        }
+        if frame % 20 < 10 {
+            keyboard.press(KeyCode::B);
+        }
+        app.update();
+    }
+    let recorded = player_snapshot(&mut app);
+    let recorded_enemies = enemy_positions(&mut app);
+    app.world.send_event(AppExit);
+    app.update();
+
+    let mut app = GameAppBuilder::new()
+        .headless(1280.0, 720.0)
+        .replay(&path)
+        .expect("replay was saved on exit")
+        .build();
+    wait_for_game_data(&mut app);
+    std::fs::remove_file(&path).unwrap();
+
+    for frame in 0..180 {
+        // live input, which playback ignores
+        if frame == 60 {
+            app.world.send_event(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(KeyCode::Space),
+                state: ButtonState::Pressed,
+            });
+        }
+        app.update();
+    }
+
+    assert_eq!(
+        app.world.resource::<State<GameState>>().0,
+        GameState::Playing
+    );
+    assert_eq!(player_snapshot(&mut app), recorded);
+    assert_eq!(enemy_positions(&mut app), recorded_enemies);
+}
+
+#[test]
+fn restart_clears_the_field_and_replays_the_seed() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(9));
+
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(KeyCode::B),
+        state: ButtonState::Pressed,
+    });
+    for _ in 0..120 {
+        app.update();
+    }
+    app.world
+        .spawn((PowerUp, ItemVariant::Item("mushroom".to_string())));
+    app.world.spawn(Bone);
+
+    // no one makes the high scores, so nothing waits on initials
+    let mut player_query = app
+        .world
+        .query_filtered::<(&mut Vitality, &mut Lives, &mut Score), With<Player>>();
+    for (mut vitality, mut lives, mut score) in player_query.iter_mut(&mut app.world) {
+        *vitality = Vitality::Dead;
+        lives.count = 0;
+        score.value = 0;
+    }
+    for _ in 0..2 {
+        app.update();
+    }
+    assert_eq!(
+        app.world.resource::<State<GameState>>().0,
+        GameState::GameOver
+    );
+
+    // how many enemies, power-ups, fireballs and bones are out there
+    let leftovers = |app: &mut App| {
+        [
+            app.world
+                .query_filtered::<(), With<Enemy>>()
+                .iter(&app.world)
+                .count(),
+            app.world
+                .query_filtered::<(), With<PowerUp>>()
+                .iter(&app.world)
+                .count(),
+            app.world
+                .query_filtered::<(), With<Fireball>>()
+                .iter(&app.world)
+                .count(),
+            app.world
+                .query_filtered::<(), With<Bone>>()
+                .iter(&app.world)
+                .count(),
+        ]
+    };
+    assert!(leftovers(&mut app).iter().all(|count| *count > 0));
+
+    tap_key(&mut app, KeyCode::R);
+    assert_eq!(leftovers(&mut app), [0; 4]);
+    let game_rng = app.world.resource::<GameRng>();
+    assert_eq!(game_rng.enemies, GameRng::new(9).enemies);
+    assert_eq!(game_rng.loot, GameRng::new(9).loot);
+
+    app.update();
+    assert_eq!(
+        app.world.resource::<State<GameState>>().0,
+        GameState::Playing
+    );
+}
+
+#[test]
+fn game_over_records_high_scores() {
+    let path = std::env::temp_dir().join(format!(
+        "bevy-minimal-{}-high_scores.ron",
+        std::process::id()
+    ));
+
+    let mut app = GameAppBuilder::new()
+        .headless(1280.0, 720.0)
+        .start_playing()
+        .initials("abc", "xyz")
+        .high_score_file(&path)
+        .build();
+    wait_for_game_data(&mut app);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let mut player_query = app
+        .world
+        .query_filtered::<(&PlayerVariant, &mut Vitality, &mut Lives, &mut Score), With<Player>>();
+    for (player_variant, mut vitality, mut lives, mut score) in
+        player_query.iter_mut(&mut app.world)
+    {
+        *vitality = Vitality::Dead;
+        lives.count = 0;
+        score.value = match player_variant {
+            PlayerVariant::One => 12,
+            PlayerVariant::Two => 30,
+        };
+    }
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // nothing is entered until each player has had their say
+    assert!(!path.exists());
+    let pending: Vec<_> = app
+        .world
+        .resource::<InitialsEntry>()
+        .pending
+        .iter()
+        .map(|pending_score| (pending_score.player_variant.clone(), pending_score.score))
+        .collect();
+    assert_eq!(
+        pending,
+        [(PlayerVariant::One, 12), (PlayerVariant::Two, 30)]
+    );
+
+    // player one keeps the initials given up front
+    tap_key(&mut app, KeyCode::Return);
+    assert!(path.exists());
+
+    // player two types theirs in, and R only types an R
+    for key_code in [
+        KeyCode::K,
+        KeyCode::Key1,
+        KeyCode::Back,
+        KeyCode::R,
+        KeyCode::Numpad7,
+        KeyCode::O,
+    ] {
+        tap_key(&mut app, key_code);
+    }
+    assert_eq!(app.world.resource::<InitialsEntry>().typed, "KR7");
+    assert_eq!(
+        app.world.resource::<State<GameState>>().0,
+        GameState::GameOver
+    );
+    tap_key(&mut app, KeyCode::Return);
+    assert!(!app.world.contains_resource::<InitialsEntry>());
+    assert_eq!(
+        app.world.resource::<Initials>().get(&PlayerVariant::Two),
+        "KR7"
+    );
+
+    let high_scores = HighScores::load(&path).expect("high scores were saved once entered");
+    std::fs::remove_file(&path).unwrap();
+
+    let table = high_scores.table(GameMode::Coop);
+    assert_eq!(table.len(), 2);
+    assert_eq!((table[0].initials.as_str(), table[0].score), ("KR7", 30));
+    assert_eq!((table[1].initials.as_str(), table[1].score), ("ABC", 12));
+    assert_eq!(app.world.resource::<HighScores>(), &high_scores);
+}
+
+/// Press and let go of `key_code` within one update.
+fn tap_key(app: &mut App, key_code: KeyCode) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+        });
+    }
+    app.update();
+}
+
+fn sprite_manifest() -> SpriteManifest {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/manifest.sprites.ron");
+    ron::de::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn sprite_manifest_is_checked_on_load() {
+    assert!(sprite_manifest().build().is_ok());
+
+    let builds = |change: &dyn Fn(&mut SpriteManifest)| {
+        let mut manifest = sprite_manifest();
+        change(&mut manifest);
+        manifest.build().is_ok()
+    };
+    assert!(!builds(&|manifest| {
+        manifest.sheets.remove("bullet");
+    }));
+    assert!(!builds(&|manifest| {
+        manifest
+            .sheets
+            .get_mut("player_dead")
+            .unwrap()
+            .animations
+            .remove("hurt");
+    }));
+    assert!(!builds(&|manifest| {
+        manifest.sheets.get_mut("explosion").unwrap().columns = 0;
+    }));
+    assert!(!builds(&|manifest| {
+        let zombie = manifest.sheets.get_mut("zombie").unwrap();
+        zombie.animations.get_mut("walk").unwrap().last = zombie.columns * zombie.rows;
+    }));
+    assert!(!builds(&|manifest| {
+        let explosion = manifest.sheets.get_mut("explosion").unwrap();
+        explosion.animations.get_mut("explode").unwrap().frame_time = Some(-0.1);
+    }));
+
+    // a broken reload leaves the running game on the sheets it had
+    let mut app = playing_app();
+    let handle = app
+        .world
+        .resource::<ManifestHandle<SpriteManifest>>()
+        .0
+        .clone();
+    let reload = |app: &mut App, change: &dyn Fn(&mut SpriteManifest)| {
+        let mut sprite_manifests = app.world.resource_mut::<Assets<SpriteManifest>>();
+        change(sprite_manifests.get_mut(&handle).unwrap());
+        // announced, checked, then registered, an update each
+        for _ in 0..3 {
+            app.update();
+        }
+    };
+    reload(&mut app, &|manifest| {
+        manifest.sheets.remove("bullet");
+    });
+    assert!(app
+        .world
+        .resource::<SpriteRegistry>()
+        .sheets
+        .contains_key("bullet"));
+    // and a good one is picked up
+    reload(&mut app, &|manifest| {
+        *manifest = sprite_manifest();
+        manifest.sheets.get_mut("tree").unwrap().scale = 7.0;
+    });
+    let sprite_registry = app.world.resource::<SpriteRegistry>();
+    assert_eq!(sprite_registry.get("tree").sheet.scale, 7.0);
+}
+
+#[test]
+fn colliders_follow_scale_offset_and_flip() {
+    let enemy = Collider::aabb(10.0, 20.0).with_offset(Vec2::new(-4.0, 0.0));
+    let fireball = Collider::circle(2.0);
+
+    // Scaled by 3, the box spans x -27..3 around an entity at the origin.
+    let enemy_transform = Transform::from_scale(Vec3::splat(3.0));
+    assert!(colliding(
+        (&enemy, &enemy_transform),
+        (&fireball, &Transform::from_xyz(-25.0, 0.0, 0.0)),
+    ));
+    assert!(!colliding(
+        (&enemy, &enemy_transform),
+        (&fireball, &Transform::from_xyz(6.0, 0.0, 0.0)),
+    ));
+
+    // Mirrored art mirrors the offset, so the box spans x -3..27.
+    let flipped_transform = enemy_transform.with_rotation(Quat::from_rotation_y(PI));
+    assert!(colliding(
+        (&enemy, &flipped_transform),
+        (&fireball, &Transform::from_xyz(25.0, 0.0, 0.0)),
+    ));
+    assert!(!colliding(
+        (&enemy, &flipped_transform),
+        (&fireball, &Transform::from_xyz(-6.0, 0.0, 0.0)),
+    ));
+}
+
+#[test]
+fn spatial_grid_finds_every_overlap() {
+    let mut rng = ChaCha8Rng::seed_from_u64(9);
+    let mut random_bounds = |collider: Collider| {
+        let transform = Transform::from_xyz(
+            rng.gen_range(-1000.0..1000.0),
+            rng.gen_range(-1000.0..1000.0),
+            0.0,
+        )
+        .with_scale(Vec3::splat(rng.gen_range(1.0..3.0)));
+        ColliderBounds::new(&collider, &transform)
+    };
+
+    let mut spatial_grid = SpatialGrid::new(SPATIAL_GRID_CELL_SIZE);
+    let mut placed = Vec::new();
+    for index in 0..2000 {
+        let entity = Entity::from_raw(index);
+        let bounds = random_bounds(Collider::aabb(12.0, 30.0));
+        spatial_grid.insert(entity, bounds);
+        placed.push((entity, bounds));
+    }
+
+    for _ in 0..200 {
+        let bounds = random_bounds(Collider::circle(20.0));
+        let expected: Vec<Entity> = placed
+            .iter()
+            .filter(|(_, other)| bounds.intersects(other))
+            .map(|(entity, _)| *entity)
+            .collect();
+        assert_eq!(spatial_grid.overlapping(&bounds), expected);
+    }
+}
+
+#[test]
+fn enemy_killed_event_scores_and_leaves_a_corpse() {
+    let mut app = playing_app();
+
+    app.world.send_event(EnemyKilled {
+        variant: EnemyVariant("zombie".to_string()),
+        killer: PlayerVariant::One,
+        cause: KillCause::Diamond,
+        elite: false,
+        position: Vec3::new(0.0, 0.0, 10.0),
+    });
+    app.update();
+
+    let mut score_query = app.world.query_filtered::<&Score, With<Player>>();
+    let score = score_query.single(&app.world).value;
+    assert_eq!(score, DIAMOND_SCORE_MULTIPLIER);
+
+    let mut corpse_query = app.world.query_filtered::<&EnemyVariant, With<EnemyDead>>();
+    let corpses: Vec<_> = corpse_query.iter(&app.world).collect();
+    assert_eq!(corpses, [&EnemyVariant("zombie".to_string())]);
+
+    let mut explosion_query = app.world.query_filtered::<(), With<Explosion>>();
+    assert_eq!(explosion_query.iter(&app.world).count(), 1);
+}
+
+#[test]
+fn explosion_plays_once_then_despawns() {
+    let mut app = playing_app();
+
+    app.world.send_event(EnemyKilled {
+        variant: EnemyVariant("zombie".to_string()),
+        killer: PlayerVariant::One,
+        cause: KillCause::Fireball,
+        elite: false,
+        position: Vec3::new(0.0, 0.0, 10.0),
+    });
+    app.update();
+
+    let mut explosion_query = app
+        .world
+        .query_filtered::<&AnimationController, With<Explosion>>();
+    let explosion = explosion_query.single(&app.world);
+    assert_eq!(explosion.current(), "explode");
+    assert!(!explosion.finished());
+
+    for _ in 0..60 {
+        app.update();
+    }
+
+    assert_eq!(explosion_query.iter(&app.world).count(), 0);
+
+    let mut corpse_query = app
+        .world
+        .query_filtered::<&AnimationController, With<EnemyDead>>();
+    assert!(corpse_query.single(&app.world).finished());
+}
+
+#[test]
+fn goblin_takes_several_fireballs_to_kill() {
+    let mut app = playing_app();
+
+    let goblin = spawn_enemy_at(&mut app, "goblin", Vec2::ZERO);
+
+    let hit = |app: &mut App| {
+        let fireball = app
+            .world
+            .spawn((
+                Fireball,
+                PlayerVariant::One,
+                Damage {
+                    amount: 1,
+                    knockback: 600.0,
+                },
+                // shot straight up
+                Velocity { x: 0.0, y: 1.0 },
+            ))
+            .id();
+        app.world.send_event(FireballHitEnemy {
+            fireball,
+            enemy: goblin,
+        });
+        app.update();
+    };
+
+    for hits in 1..4 {
+        hit(&mut app);
+        assert_eq!(app.world.get::<Health>(goblin).unwrap().current, 4 - hits);
+        assert!(app.world.get::<Hurt>(goblin).is_some());
+    }
+    // and pushed back the way the shots were going
+    assert!(app.world.get::<Transform>(goblin).unwrap().translation.y > 0.0);
+
+    hit(&mut app);
+    assert!(app.world.get_entity(goblin).is_none());
+
+    let mut corpse_query = app.world.query_filtered::<&EnemyVariant, With<EnemyDead>>();
+    let corpses: Vec<_> = corpse_query.iter(&app.world).collect();
+    assert_eq!(corpses, [&EnemyVariant("goblin".to_string())]);
+}
+
+#[test]
+fn hurt_and_shooting_show_on_the_sprites() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop));
+    let clip = |app: &App, entity: Entity| {
+        app.world
+            .get::<AnimationController>(entity)
+            .unwrap()
+            .current()
+            .to_string()
+    };
+
+    let mut player_query = app
+        .world
+        .query_filtered::<(Entity, &PlayerVariant), With<Player>>();
+    let players: Vec<_> = player_query
+        .iter(&app.world)
+        .map(|(player, variant)| (variant.clone(), player))
+        .collect();
+    let player = |variant: PlayerVariant| {
+        players
+            .iter()
+            .find(|(player_variant, _)| *player_variant == variant)
+            .unwrap()
+            .1
+    };
+    let (player_one, player_two) = (player(PlayerVariant::One), player(PlayerVariant::Two));
+
+    app.world
+        .get_mut::<PlayerInput>(player_two)
+        .unwrap()
+        .fire_pressed = true;
+    app.update();
+    assert_eq!(clip(&app, player_two), "shoot");
+
+    // a shield taking the hit still staggers the player
+    drop_on_player(
+        &mut app,
+        player_one,
+        ItemVariant::Item("shield".to_string()),
+    );
+    app.update();
+    let goblin = spawn_enemy_at(&mut app, "goblin", Vec2::new(600.0, 0.0));
+    app.world.send_event(EnemyHitPlayer {
+        enemy: goblin,
+        player: player_one,
+    });
+    app.update();
+    app.update();
+    assert_eq!(clip(&app, player_one), "hurt");
+
+    // enemies reel while hurt, then walk on
+    let fireball = app
+        .world
+        .spawn((
+            Fireball,
+            PlayerVariant::One,
+            Damage {
+                amount: 1,
+                knockback: 600.0,
+            },
+            Velocity::default(),
+        ))
+        .id();
+    app.world.send_event(FireballHitEnemy {
+        fireball,
+        enemy: goblin,
+    });
+    app.update();
+    app.update();
+    assert_eq!(clip(&app, goblin), "hurt");
+    while app.world.get::<Hurt>(goblin).is_some() {
+        app.update();
+    }
+    assert_eq!(clip(&app, goblin), "walk");
+}
+
+const SHAMBLE: &str = "Shamble(homing: 0.25)";
+
+fn enemy_manifest(enemies: &str) -> EnemyManifest {
+    ron::de::from_str(&format!("(enemies: {{ {} }})", enemies)).unwrap()
+}
+
+fn zombie_entry(speed: &str, spawn_weight: f32, behaviour: &str) -> String {
+    format!(
+        r#""zombie": (
+            sprite: "zombie",
+            death_sprite: "zombie_dead",
+            speed: {},
+            health: 1,
+            score: 1,
+            spawn_weight: {:?},
+            collider: (shape: Aabb(width: 12.0, height: 26.0)),
+            behaviour: {},
+        )"#,
+        speed, spawn_weight, behaviour
+    )
+}
+
+#[test]
+fn enemy_manifest_is_checked_on_load() {
+    // a fixed speed is a range too
+    let fixed_speed = enemy_manifest(&zombie_entry("(min: 0.05, max: 0.05)", 1.0, SHAMBLE));
+    let enemy_types = fixed_speed.build().unwrap();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    assert_eq!(enemy_types.choose(&mut rng), Some("zombie"));
+
+    assert!(
+        enemy_manifest(&zombie_entry("(min: 0.1, max: 0.05)", 1.0, SHAMBLE))
+            .build()
+            .is_err()
+    );
+    assert!(
+        enemy_manifest(&zombie_entry("(min: 0.05, max: 0.1)", -1.0, SHAMBLE))
+            .build()
+            .is_err()
+    );
+    assert!(
+        enemy_manifest(&zombie_entry("(min: 0.05, max: 0.1)", 0.0, SHAMBLE))
+            .build()
+            .is_err()
+    );
+    assert!(enemy_manifest("").build().is_err());
+    let too_likely_loot = format!("{}, loot: [(item: \"coin\", chance: 1.5)]", SHAMBLE);
+    assert!(enemy_manifest(&zombie_entry(
+        "(min: 0.05, max: 0.1)",
+        1.0,
+        &too_likely_loot
+    ))
+    .build()
+    .is_err());
+
+    // behaviours that would tick a zero-length timer or run it backwards
+    let behaviour_builds = |behaviour: &str| {
+        enemy_manifest(&zombie_entry("(min: 0.05, max: 0.1)", 1.0, behaviour))
+            .build()
+            .is_ok()
+    };
+    assert!(behaviour_builds(
+        "KeepDistance(distance: 450.0, throw_interval: 1.2, bone_speed: 0.6, bones: 3)"
+    ));
+    assert!(!behaviour_builds(
+        "KeepDistance(distance: 450.0, throw_interval: 0.0, bone_speed: 0.6, bones: 3)"
+    ));
+    assert!(behaviour_builds(
+        "Charge(range: 500.0, wind_up: 0.0, speed: 1.2)"
+    ));
+    assert!(!behaviour_builds(
+        "Charge(range: 500.0, wind_up: -0.6, speed: 1.2)"
+    ));
+}
+
+#[test]
+fn enemies_without_sprites_are_left_out() {
+    let mut app = playing_app();
+
+    {
+        let mut enemy_types = app.world.resource_mut::<EnemyTypes>();
+        enemy_types.types.get_mut("skeleton").unwrap().sprite = "skeletn".to_string();
+        enemy_types.types.get_mut("goblin").unwrap().death_animation = "fall".to_string();
+    }
+    app.update();
+
+    let enemy_types = app.world.resource::<EnemyTypes>();
+    assert!(!enemy_types.types.contains_key("skeleton"));
+    assert!(!enemy_types.types.contains_key("goblin"));
+    assert!(enemy_types.types.contains_key("zombie"));
+
+    // and a kill of an enemy that has since been left out is let go
+    app.world.send_event(EnemyKilled {
+        variant: EnemyVariant("goblin".to_string()),
+        killer: PlayerVariant::One,
+        cause: KillCause::Fireball,
+        elite: false,
+        position: Vec3::ZERO,
+    });
+    app.update();
+    let mut score_query = app.world.query_filtered::<&Score, With<Player>>();
+    assert_eq!(score_query.single(&app.world).value, 0);
+    let mut corpse_query = app.world.query_filtered::<(), With<EnemyDead>>();
+    assert_eq!(corpse_query.iter(&app.world).count(), 0);
+}
+
+#[test]
+fn bosses_that_cannot_fight_are_left_out() {
+    let boss_types = |phases: &str| {
+        let manifest: EnemyManifest = ron::de::from_str(&format!(
+            r#"(
+                enemies: {{ {} }},
+                boss: Some((
+                    name: "Zombie King",
+                    sprite: "zombie",
+                    death_sprite: "zombie_dead",
+                    health: 10,
+                    score: 10,
+                    collider: (shape: Aabb(width: 12.0, height: 26.0)),
+                    trigger: Wave(1),
+                    minion: "zombie",
+                    phases: [{}],
+                )),
+            )"#,
+            zombie_entry("(min: 0.05, max: 0.1)", 1.0, SHAMBLE),
+            phases
+        ))
+        .unwrap();
+        manifest.build().unwrap()
+    };
+
+    let fights = |phases: &str| boss_types(phases).boss.is_some();
+    assert!(fights(
+        "(health: 1.0, speed: 0.2, attacks: [], attack_interval: 2.0)"
+    ));
+    assert!(!fights(""));
+    assert!(!fights(
+        "(health: 1.0, speed: 0.2, attacks: [], attack_interval: 0.0)"
+    ));
+    assert!(!fights(
+        "(health: 1.0, speed: 0.2, attacks: [], attack_interval: 2.0, minions: 3)"
+    ));
+
+    // the rest of the manifest still loads
+    assert!(boss_types("").types.contains_key("zombie"));
+}
+
+#[test]
+fn enemies_spawn_with_their_behaviour() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop).seed(5));
+
+    // the debug key brings in a random mix of every type
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(KeyCode::F1),
+        state: ButtonState::Pressed,
+    });
+    app.update();
+
+    let mut enemy_query = app.world.query_filtered::<(
+        &EnemyVariant,
+        Option<&Shambler>,
+        Option<&Skirmisher>,
+        Option<&Charger>,
+    ), With<Enemy>>();
+    let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+    assert!(!enemies.is_empty());
+
+    for (variant, shambler, skirmisher, charger) in enemies {
+        let behaviour = (shambler.is_some(), skirmisher.is_some(), charger.is_some());
+        match variant.0.as_str() {
+            "zombie" => assert_eq!(behaviour, (true, false, false)),
+            "skeleton" => assert_eq!(behaviour, (false, true, false)),
+            "goblin" => assert_eq!(behaviour, (false, false, true)),
+            other => panic!("unexpected enemy type {}", other),
+        }
+    }
+}
+
+/// The only player's translation, for placing enemies around them.
+fn player_translation(app: &mut App) -> Vec3 {
+    let mut player_query = app.world.query_filtered::<&Transform, With<Player>>();
+    player_query.single(&app.world).translation
+}
+
+#[test]
+fn skeletons_keep_their_distance_and_throw_bones() {
+    let mut app = playing_app();
+    let player = player_translation(&mut app);
+
+    let skeleton = spawn_enemy_at(
+        &mut app,
+        "skeleton",
+        Vec2::new(player.x + 150.0, player.y + 100.0),
+    );
+    let bones = app.world.get::<Skirmisher>(skeleton).unwrap().bones;
+    let translation = |app: &App| app.world.get::<Transform>(skeleton).unwrap().translation;
+
+    // too close, so it backs off while lining up with the player
+    for _ in 0..30 {
+        app.update();
+    }
+    let backed_off = translation(&app);
+    assert!(backed_off.x > player.x + 150.0);
+    assert!((backed_off.y - player.y).abs() < 100.0);
+
+    // and throws a bone at them once its throw timer runs out
+    let bones_left = |app: &App| app.world.get::<Skirmisher>(skeleton).unwrap().bones;
+    for _ in 0..120 {
+        if bones_left(&app) < bones {
+            break;
+        }
+        app.update();
+    }
+    assert_eq!(bones_left(&app), bones - 1);
+    let mut bone_query = app.world.query_filtered::<(), With<Bone>>();
+    assert_eq!(bone_query.iter(&app.world).count(), 1);
+}
+
+#[test]
+fn goblins_wind_up_then_charge() {
+    let mut app = playing_app();
+    let player = player_translation(&mut app);
+
+    let start = Vec2::new(player.x + 300.0, player.y + 50.0);
+    let goblin = spawn_enemy_at(&mut app, "goblin", start);
+    let translation = |app: &App| app.world.get::<Transform>(goblin).unwrap().translation;
+    let state = |app: &App| app.world.get::<Charger>(goblin).unwrap().state;
+
+    // in range, so it stops to wind up, rearing back as it does
+    app.update();
+    assert_eq!(state(&app), ChargeState::WindUp);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(state(&app), ChargeState::WindUp);
+    assert!(translation(&app).x > start.x);
+
+    // then charges at where the player was
+    for _ in 0..60 {
+        app.update();
+    }
+    let ChargeState::Charging(direction) = state(&app) else {
+        panic!("never charged");
+    };
+    assert!(direction.x < 0.0 && direction.y < 0.0);
+    assert!(translation(&app).x < start.x);
+}
+
+#[test]
+fn wave_director_plays_the_scripted_waves() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop));
+
+    let wave_number = |app: &App| app.world.resource::<WaveDirector>().number;
+    let step_until = |app: &mut App, done: &dyn Fn(&mut App) -> bool| {
+        for _ in 0..1200 {
+            if done(app) {
+                return;
+            }
+            app.update();
+        }
+        panic!("gave up waiting");
+    };
+
+    step_until(&mut app, &|app| wave_number(app) == 1);
+    app.update();
+
+    let mut enemy_query = app
+        .world
+        .query_filtered::<(Entity, &EnemyVariant), With<Enemy>>();
+    let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+    assert_eq!(enemies.len(), 5);
+    assert!(enemies.iter().all(|(_, variant)| variant.0 == "zombie"));
+
+    let mut announcement_query = app.world.query::<&WaveAnnouncement>();
+    assert_eq!(announcement_query.iter(&app.world).count(), 1);
+
+    // The wave isn't over while a group is still to come, even with nobody
+    // left on the field.
+    let clear_field = |app: &mut App| {
+        let mut enemy_query = app.world.query_filtered::<Entity, With<Enemy>>();
+        let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+        for enemy in enemies {
+            app.world.despawn(enemy);
+        }
+    };
+    clear_field(&mut app);
+    app.update();
+    assert_eq!(wave_number(&app), 1);
+    assert_eq!(
+        app.world.resource::<WaveDirector>().phase,
+        WavePhase::Running
+    );
+
+    step_until(&mut app, &|app| {
+        let mut enemy_query = app.world.query_filtered::<(), With<Enemy>>();
+        enemy_query.iter(&app.world).count() == 3
+    });
+    clear_field(&mut app);
+    step_until(&mut app, &|app| wave_number(app) == 2);
+}
+
+#[test]
+fn formations_fit_a_short_playfield() {
+    let playfield = Playfield {
+        height: 80.0,
+        ..Default::default()
+    };
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    for formation in [
+        Formation::Scatter,
+        Formation::Line,
+        Formation::Column,
+        Formation::Wedge,
+    ] {
+        for side in [SpawnSide::Right, SpawnSide::Top, SpawnSide::Bottom] {
+            let positions = formation_positions(formation, side, 3, &playfield, &mut rng);
+            assert!(positions.iter().all(|position| position.y == 0.0));
+        }
+    }
+}
+
+#[test]
+fn reloaded_waves_keep_the_director_on_a_wave() {
+    let mut app = playing_app();
+
+    {
+        let mut wave_director = app.world.resource_mut::<WaveDirector>();
+        wave_director.number = 3;
+        wave_director.script = 2;
+        wave_director.phase = WavePhase::Running;
+        wave_director.spawned = vec![true; 2];
+    }
+    let manifest: WaveManifest = ron::de::from_str(
+        r#"(waves: [(
+            groups: [
+                (enemy: "zombie", count: 1),
+                (enemy: "dragon", count: 1),
+                (enemy: "zombie", count: 1, delay: 1000.0),
+            ],
+            clear: NoEnemiesLeft,
+            pause: 2.0,
+        )])"#,
+    )
+    .unwrap();
+    app.world.insert_resource(manifest.build().unwrap());
+    app.update();
+
+    let wave_director = app.world.resource::<WaveDirector>();
+    assert_eq!(wave_director.script, 0);
+    assert_eq!(wave_director.spawned, [true, true, false]);
+
+    // waits that would run a timer backwards aren't loaded
+    let negative_pause: WaveManifest =
+        ron::de::from_str(r#"(waves: [(groups: [], clear: NoEnemiesLeft, pause: -2.0)])"#).unwrap();
+    assert!(negative_pause.build().is_err());
+}
+
+#[test]
+fn difficulty_ramps_with_preset_and_score() {
+    let mut app = playing_app_with(|builder| builder.difficulty(DifficultyPreset::Hard));
+
+    for _ in 0..1200 {
+        if app.world.resource::<WaveDirector>().number == 1 {
+            break;
+        }
+        app.update();
+    }
+    app.update();
+
+    // Hard brings in more zombies than the five scripted, each tougher.
+    let mut enemy_query = app
+        .world
+        .query_filtered::<(&EnemyVariant, &Health), With<Enemy>>();
+    let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+    assert_eq!(enemies.len(), 6);
+    assert!(enemies
+        .iter()
+        .all(|(variant, health)| variant.0 == "zombie" && health.max >= 3));
+
+    let spawn_rate = app.world.resource::<Difficulty>().spawn_rate;
+    assert!(spawn_rate > 1.0);
+
+    let mut score_query = app.world.query_filtered::<&mut Score, With<Player>>();
+    score_query.single_mut(&mut app.world).value = 500;
+    app.update();
+
+    assert!(app.world.resource::<Difficulty>().spawn_rate > spawn_rate + 1.0);
+}
+
+#[test]
+fn difficulty_manifest_needs_every_preset() {
+    let curves = |enemy_speed: &str| {
+        format!(
+            "(
+                spawn_rate: (start: 1.0, max: 1.0),
+                enemy_speed: {},
+                enemy_health: (start: 1.0, max: 1.0),
+                elite_chance: (start: 0.0, max: 0.0),
+            )",
+            enemy_speed
+        )
+    };
+    let manifest = |presets: &[DifficultyPreset], enemy_speed: &str| {
+        let presets: Vec<_> = presets
+            .iter()
+            .map(|preset| format!("{:?}: {}", preset, curves(enemy_speed)))
+            .collect();
+        ron::de::from_str::<DifficultyManifest>(&format!("(presets: {{ {} }})", presets.join(", ")))
+            .unwrap()
+            .build()
+    };
+
+    let no_hard = [DifficultyPreset::Easy, DifficultyPreset::Normal];
+    assert!(manifest(&no_hard, "(start: 1.0, max: 1.0)").is_err());
+    assert!(manifest(&DifficultyPreset::ALL, "(start: 1.0, max: inf)").is_err());
+
+    // enemies never slow to a stop or walk backwards
+    let presets = manifest(&DifficultyPreset::ALL, "(start: -1.0, max: 1.0)").unwrap();
+    let mut app = playing_app();
+    app.world.insert_resource(presets);
+    app.update();
+    assert!(app.world.resource::<Difficulty>().enemy_speed > 0.0);
+}
+
+#[test]
+fn weapon_pickup_swaps_the_players_gun() {
+    let mut app = playing_app();
+
+    let mut player_query = app
+        .world
+        .query_filtered::<(Entity, &Weapon), With<Player>>();
+    let (player, weapon) = player_query.single(&app.world);
+    assert_eq!(weapon.kind, STARTING_WEAPON);
+
+    let pickup = drop_on_player(&mut app, player, ItemVariant::Weapon);
+    app.world.entity_mut(pickup).insert(WeaponPickup {
+        kind: "spread".to_string(),
+    });
+    app.update();
+
+    assert_eq!(app.world.get::<Weapon>(player).unwrap().kind, "spread");
+    let mut pickup_query = app.world.query_filtered::<(), With<WeaponPickup>>();
+    assert_eq!(pickup_query.iter(&app.world).count(), 0);
+
+    app.world
+        .get_mut::<PlayerInput>(player)
+        .unwrap()
+        .fire_pressed = true;
+    app.update();
+
+    let mut fireball_query = app.world.query_filtered::<&Velocity, With<Fireball>>();
+    let velocities: Vec<_> = fireball_query.iter(&app.world).collect();
+    assert_eq!(velocities.len(), 5);
+    assert!(velocities.iter().any(|velocity| velocity.y > 0.0));
+    assert!(velocities.iter().any(|velocity| velocity.y < 0.0));
+
+    // a reload that drops the weapon leaves the player with the starting one
+    app.world
+        .resource_mut::<WeaponTypes>()
+        .types
+        .remove("spread");
+    app.update();
+    assert_eq!(
+        app.world.get::<Weapon>(player).unwrap().kind,
+        STARTING_WEAPON
+    );
+
+    // and weapons that could never fire again aren't loaded
+    let stalled: WeaponManifest = ron::de::from_str(
+        r#"(weapons: {
+            "single": (
+                name: "Fireball",
+                projectile: "fireball",
+                speed: 1.0,
+                damage: 1,
+                knockback: 600.0,
+                fire_rate: 0.0,
+            ),
+        })"#,
+    )
+    .unwrap();
+    assert!(stalled.build().is_err());
+    let single = |fields: &str| {
+        ron::de::from_str::<WeaponManifest>(&format!(
+            r#"(weapons: {{
+                "single": (
+                    name: "Fireball",
+                    projectile: "fireball",
+                    speed: 1.0,
+                    damage: 1,
+                    knockback: 600.0,
+                    fire_rate: 4.0,
+                    {}
+                ),
+            }})"#,
+            fields
+        ))
+        .unwrap()
+        .build()
+    };
+    assert!(single("").is_ok());
+    assert!(single("projectiles: 0,").is_err());
+    assert!(single("charge: Some((time: 1.0, damage_multiplier: 4, scale: 0.0)),").is_err());
+
+    // and ones whose projectiles can't be drawn are left out, bar the one
+    // every player starts with
+    {
+        let mut weapon_types = app.world.resource_mut::<WeaponTypes>();
+        weapon_types.types.get_mut("rapid").unwrap().projectile = "rapd".to_string();
+        weapon_types
+            .types
+            .get_mut(STARTING_WEAPON)
+            .unwrap()
+            .projectile = "fireblal".to_string();
+    }
+    app.update();
+    let weapon_types = app.world.resource::<WeaponTypes>();
+    assert!(!weapon_types.types.contains_key("rapid"));
+    assert_eq!(
+        weapon_types.get(STARTING_WEAPON).projectile,
+        FALLBACK_PROJECTILE
+    );
+}
+
+#[test]
+fn mushroom_makes_the_player_invincible_for_a_while() {
+    let mut app = playing_app();
+    let mushroom_time = app.world.resource::<ItemTypes>().get("mushroom").duration;
+
+    let mut player_query = app.world.query_filtered::<Entity, With<Player>>();
+    let player = player_query.single(&app.world);
+
+    drop_on_player(&mut app, player, ItemVariant::Item("mushroom".to_string()));
+    app.update();
+    assert!(app
+        .world
+        .get::<ActiveEffects>(player)
+        .unwrap()
+        .is_active(Effect::Invincible));
+
+    let goblin = spawn_enemy_at(&mut app, "goblin", Vec2::new(600.0, 0.0));
+    let touch = |app: &mut App| {
+        app.world.send_event(EnemyHitPlayer {
+            enemy: goblin,
+            player,
+        });
+        app.update();
+    };
+
+    touch(&mut app);
+    assert_eq!(*app.world.get::<Vitality>(player).unwrap(), Vitality::Alive);
+    assert_eq!(app.world.get::<Lives>(player).unwrap().count, 3);
+    assert!(app.world.get_entity(goblin).is_some());
+
+    app.world
+        .get_mut::<ActiveEffects>(player)
+        .unwrap()
+        .tick(Duration::from_secs_f32(mushroom_time));
+    app.update();
+    assert!(!app
+        .world
+        .get::<ActiveEffects>(player)
+        .unwrap()
+        .is_active(Effect::Invincible));
+    assert_eq!(
+        *app.world.get::<Visibility>(player).unwrap(),
+        Visibility::Inherited
+    );
+
+    touch(&mut app);
+    assert_eq!(*app.world.get::<Vitality>(player).unwrap(), Vitality::Dead);
+    assert_eq!(app.world.get::<Lives>(player).unwrap().count, 2);
+}
+
+#[test]
+fn power_ups_run_out_per_player() {
+    let mut app = playing_app_with(|builder| builder.game_mode(GameMode::Coop));
+
+    let mut player_query = app
+        .world
+        .query_filtered::<(Entity, &PlayerVariant), With<Player>>();
+    let players: Vec<_> = player_query
+        .iter(&app.world)
+        .map(|(player, variant)| (variant.clone(), player))
+        .collect();
+    let player = |variant: PlayerVariant| {
+        players
+            .iter()
+            .find(|(player_variant, _)| *player_variant == variant)
+            .unwrap()
+            .1
+    };
+    let (player_one, player_two) = (player(PlayerVariant::One), player(PlayerVariant::Two));
+
+    let diamond_time = app.world.resource::<ItemTypes>().get("diamond").duration;
+    let mushroom_time = app.world.resource::<ItemTypes>().get("mushroom").duration;
+
+    let pick_up = |app: &mut App, player: Entity, item: &str| {
+        drop_on_player(app, player, ItemVariant::Item(item.to_string()));
+        app.update();
+    };
+    let remaining = |app: &App, player: Entity, effect: Effect| {
+        app.world
+            .get::<ActiveEffects>(player)
+            .unwrap()
+            .remaining_secs(effect)
+    };
+
+    pick_up(&mut app, player_one, "diamond");
+    app.world
+        .get_mut::<ActiveEffects>(player_one)
+        .unwrap()
+        .tick(Duration::from_secs_f32(diamond_time / 2.0));
+    let player_one_left = remaining(&app, player_one, Effect::Diamond).unwrap();
+
+    // player two's diamond leaves player one's alone
+    pick_up(&mut app, player_two, "diamond");
+    let player_two_left = remaining(&app, player_two, Effect::Diamond).unwrap();
+    assert!(player_one_left - remaining(&app, player_one, Effect::Diamond).unwrap() < 0.1);
+    assert!(player_two_left > diamond_time - 0.1);
+
+    // a second diamond starts over, a second mushroom adds on
+    pick_up(&mut app, player_one, "diamond");
+    assert!(remaining(&app, player_one, Effect::Diamond).unwrap() > diamond_time - 0.1);
+    pick_up(&mut app, player_two, "mushroom");
+    pick_up(&mut app, player_two, "mushroom");
+    assert!(remaining(&app, player_two, Effect::Invincible).unwrap() > 2.0 * mushroom_time - 0.1);
+
+    // each player's timer shows on their own side of the HUD
+    let invincible_text_query = app
+        .world
+        .query_filtered::<(&Text, &PlayerVariant), With<InvincibleText>>();
+    let invincible_text = |app: &App, variant: PlayerVariant| {
+        invincible_text_query
+            .iter_manual(&app.world)
+            .find(|(_, text_variant)| **text_variant == variant)
+            .unwrap()
+            .0
+            .sections[0]
+            .value
+            .clone()
+    };
+    assert_eq!(invincible_text(&app, PlayerVariant::One), "");
+    assert!(invincible_text(&app, PlayerVariant::Two).starts_with("Invincible "));
+
+    app.world
+        .get_mut::<ActiveEffects>(player_one)
+        .unwrap()
+        .tick(Duration::from_secs_f32(diamond_time));
+    app.update();
+    assert_eq!(remaining(&app, player_one, Effect::Diamond), None);
+    assert!(remaining(&app, player_two, Effect::Diamond).is_some());
+}
+
+#[test]
+fn items_come_from_the_registry() {
+    let mut app = playing_app();
+
+    let mut player_query = app.world.query_filtered::<Entity, With<Player>>();
+    let player = player_query.single(&app.world);
+
+    let pick_up = |app: &mut App, item: &str| {
+        drop_on_player(app, player, ItemVariant::Item(item.to_string()));
+        app.update();
+    };
+
+    pick_up(&mut app, "extra_life");
+    assert_eq!(app.world.get::<Lives>(player).unwrap().count, 4);
+
+    // a shield takes a hit, then leaves the player invincible for a moment
+    pick_up(&mut app, "shield");
+    let goblin = spawn_enemy_at(&mut app, "goblin", Vec2::new(600.0, 0.0));
+    app.world.send_event(EnemyHitPlayer {
+        enemy: goblin,
+        player,
+    });
+    app.update();
+    let active_effects = app.world.get::<ActiveEffects>(player).unwrap();
+    assert_eq!(*app.world.get::<Vitality>(player).unwrap(), Vitality::Alive);
+    assert!(!active_effects.is_active(Effect::Shield));
+    assert!(active_effects.is_active(Effect::Invincible));
+
+    // the spawner only puts down items whose conditions hold
+    let spawn_time = app.world.resource::<ItemSpawnTimer>().timer.duration();
+    app.world
+        .resource_mut::<ItemSpawnTimer>()
+        .timer
+        .tick(spawn_time);
+    app.update();
+    let mut item_query = app.world.query_filtered::<&ItemVariant, With<PowerUp>>();
+    let items: Vec<_> = item_query
+        .iter(&app.world)
+        .filter_map(|variant| match variant {
+            ItemVariant::Item(item) => Some(item.clone()),
+            ItemVariant::Weapon => None,
+        })
+        .collect();
+    assert_eq!(items.len(), 1);
+    let item_types = app.world.resource::<ItemTypes>();
+    assert!(item_types.types.contains_key(&items[0]));
+    assert_ne!(items[0], "extra_life");
+
+    // an item a reload took away can still be picked up, for nothing
+    app.world.resource_mut::<ItemTypes>().types.remove("coin");
+    let score_before = app.world.get::<Score>(player).unwrap().value;
+    let coin = drop_on_player(&mut app, player, ItemVariant::Item("coin".to_string()));
+    app.update();
+    assert!(app.world.get_entity(coin).is_none());
+    assert_eq!(app.world.get::<Score>(player).unwrap().value, score_before);
+
+    // and effects can't be made to run backwards
+    let backwards: ItemManifest = ron::de::from_str(
+        r#"(items: {
+            "mushroom": (
+                sprite: "mushroom",
+                animation: "idle",
+                effect: Some(Invincible),
+                duration: -5.0,
+            ),
+        })"#,
+    )
+    .unwrap();
+    assert!(backwards.build().is_err());
+
+    // or for longer than a `Duration` can hold
+    let forever: ItemManifest = ron::de::from_str(
+        r#"(items: {
+            "mushroom": (
+                sprite: "mushroom",
+                animation: "idle",
+                effect: Some(Invincible),
+                duration: 1e30,
+            ),
+        })"#,
+    )
+    .unwrap();
+    let item_types = forever.build().unwrap();
+    assert_eq!(item_types.get("mushroom").duration, MAX_EFFECT_DURATION);
+
+    // items the sprite sheets can't draw are left out
+    app.world
+        .resource_mut::<ItemTypes>()
+        .types
+        .get_mut("mushroom")
+        .unwrap()
+        .animation = "idel".to_string();
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ItemTypes>()
+        .types
+        .contains_key("mushroom"));
+}
+
+#[test]
+fn killed_enemies_drop_loot_that_scrolls_away() {
+    let mut app = playing_app_with(|builder| builder.seed(11));
+
+    let death_position = Vec3::new(0.0, 100.0, 10.0);
+    for _ in 0..20 {
+        app.world.send_event(EnemyKilled {
+            variant: EnemyVariant("goblin".to_string()),
+            killer: PlayerVariant::One,
+            cause: KillCause::Fireball,
+            elite: false,
+            position: death_position,
+        });
+    }
+    app.update();
+
+    let goblin_loot = ["coin", "mushroom", "extra_life"];
+    let mut loot_query = app
+        .world
+        .query_filtered::<(Entity, &ItemVariant, &Transform), With<LootDrop>>();
+    let drops: Vec<_> = loot_query
+        .iter(&app.world)
+        .map(|(entity, variant, transform)| (entity, variant.clone(), transform.translation))
+        .collect();
+    assert!(!drops.is_empty());
+    for (_, variant, translation) in drops.iter() {
+        let ItemVariant::Item(item) = variant else {
+            panic!("loot should be items, got {:?}", variant);
+        };
+        assert!(goblin_loot.contains(&item.as_str()));
+        assert!((translation.y - death_position.y).abs() <= 20.0);
+    }
+
+    app.update();
+    let (drop, _, dropped_at) = drops[0];
+    assert!(app.world.get::<Transform>(drop).unwrap().translation.x < dropped_at.x);
+
+    // a coin is worth points to whoever picks it up
+    let mut player_query = app.world.query_filtered::<(Entity, &Score), With<Player>>();
+    let (player, score) = player_query.single(&app.world);
+    let score_before = score.value;
+    drop_on_player(&mut app, player, ItemVariant::Item("coin".to_string()));
+    app.update();
+    let coin_score = app.world.resource::<ItemTypes>().get("coin").score;
+    assert_eq!(
+        app.world.get::<Score>(player).unwrap().value,
+        score_before + coin_score
+    );
+
+    // whatever is left lies around only so long
+    for mut loot_drop in app.world.query::<&mut LootDrop>().iter_mut(&mut app.world) {
+        let duration = loot_drop.timer.duration();
+        loot_drop.timer.tick(duration);
+    }
+    app.update();
+    assert_eq!(loot_query.iter(&app.world).count(), 0);
+
+    // loot the item manifest doesn't have never drops
+    app.world
+        .resource_mut::<EnemyTypes>()
+        .types
+        .get_mut("goblin")
+        .unwrap()
+        .loot
+        .iter_mut()
+        .for_each(|loot_entry| {
+            loot_entry.item = "coyn".to_string();
+            loot_entry.chance = 1.0;
+        });
+    app.world.send_event(EnemyKilled {
+        variant: EnemyVariant("goblin".to_string()),
+        killer: PlayerVariant::One,
+        cause: KillCause::Fireball,
+        elite: false,
+        position: death_position,
+    });
+    app.update();
+    assert_eq!(loot_query.iter(&app.world).count(), 0);
+}
+
+#[test]
+fn holding_fire_autofires_and_charges_by_weapon() {
+    let mut app = playing_app();
+
+    let mut player_query = app.world.query_filtered::<Entity, With<Player>>();
+    let player = player_query.single(&app.world);
+
+    let fire = |app: &mut App, state: ButtonState| {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::B),
+            state,
+        });
+    };
+    // keep the waves out of the way of the shots being counted
+    let step = |app: &mut App, updates: usize| {
+        for _ in 0..updates {
+            let mut enemy_query = app.world.query_filtered::<Entity, With<Enemy>>();
+            let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+            for enemy in enemies {
+                app.world.despawn(enemy);
+            }
+            app.update();
+        }
+    };
+    let fireball_damage = |app: &mut App| {
+        let mut fireball_query = app.world.query_filtered::<&Damage, With<Fireball>>();
+        let mut damage: Vec<_> = fireball_query
+            .iter(&app.world)
+            .map(|damage| damage.amount)
+            .collect();
+        damage.sort();
+        damage
+    };
+
+    // the starting weapon fires on while held, charging up as it does
+    fire(&mut app, ButtonState::Pressed);
+    step(&mut app, 70);
+    assert_eq!(fireball_damage(&mut app), [1; 7]);
+    assert_eq!(
+        app.world.get::<TextureAtlasSprite>(player).unwrap().color,
+        CHARGED_COLOR
+    );
+
+    fire(&mut app, ButtonState::Released);
+    step(&mut app, 1);
+    assert_eq!(fireball_damage(&mut app), [1, 1, 1, 1, 1, 1, 1, 4]);
+    assert_eq!(
+        app.world.get::<TextureAtlasSprite>(player).unwrap().color,
+        Color::WHITE
+    );
+
+    // rapid fire keeps firing for as long as fire is held
+    let rapid = Weapon::new("rapid", app.world.resource::<WeaponTypes>().get("rapid"));
+    *app.world.get_mut::<Weapon>(player).unwrap() = rapid;
+    step(&mut app, 120);
+    assert!(fireball_damage(&mut app).is_empty());
+
+    fire(&mut app, ButtonState::Pressed);
+    step(&mut app, 60);
+    fire(&mut app, ButtonState::Released);
+    step(&mut app, 1);
+    assert_eq!(fireball_damage(&mut app).len(), 15);
+}
+
+/// Give the only player a `kind` weapon and fire it once.
+fn fire_once_with(app: &mut App, kind: &str) {
+    let mut player_query = app.world.query_filtered::<Entity, With<Player>>();
+    let player = player_query.single(&app.world);
+    let weapon = Weapon::new(kind, app.world.resource::<WeaponTypes>().get(kind));
+    *app.world.get_mut::<Weapon>(player).unwrap() = weapon;
+
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::B),
+            state,
+        });
+        app.update();
+    }
+}
+
+#[test]
+fn piercing_shots_go_through_several_enemies() {
+    let mut app = playing_app();
+    let player = player_translation(&mut app);
+
+    let zombies: Vec<_> = [200.0, 260.0, 320.0]
+        .into_iter()
+        .map(|x| spawn_enemy_at(&mut app, "zombie", Vec2::new(player.x + x, player.y)))
+        .collect();
+
+    fire_once_with(&mut app, "lance");
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(zombies
+        .iter()
+        .all(|zombie| app.world.get_entity(*zombie).is_none()));
+    let mut corpse_query = app.world.query_filtered::<(), With<EnemyDead>>();
+    assert_eq!(corpse_query.iter(&app.world).count(), 3);
+}
+
+#[test]
+fn bouncing_shots_glance_off_the_edges() {
+    let mut app = playing_app();
+    let bounces = app.world.resource::<WeaponTypes>().get("bouncing").bounces;
+    let playfield = *app.world.resource::<Playfield>();
+
+    fire_once_with(&mut app, "bouncing");
+    let mut fireball_query = app
+        .world
+        .query_filtered::<(&Transform, &Bouncing), With<Fireball>>();
+    assert_eq!(fireball_query.iter(&app.world).count(), 2);
+
+    // kept on the playfield until they run out of bounces
+    let mut bounced = false;
+    for _ in 0..600 {
+        let mut enemy_query = app.world.query_filtered::<Entity, With<Enemy>>();
+        let enemies: Vec<_> = enemy_query.iter(&app.world).collect();
+        for enemy in enemies {
+            app.world.despawn(enemy);
+        }
+        app.update();
+
+        for (transform, bouncing) in fireball_query.iter(&app.world) {
+            bounced |= bouncing.remaining < bounces;
+            if bouncing.remaining > 0 {
+                assert!(transform.translation.x.abs() < playfield.width / 2.0 + 20.0);
+                assert!(transform.translation.y.abs() < playfield.height / 2.0 + 20.0);
+            }
+        }
+    }
+    assert!(bounced);
+    assert_eq!(fireball_query.iter(&app.world).count(), 0);
+}
+
+#[test]
+fn shots_go_the_way_the_player_last_moved() {
+    let mut app = playing_app();
+
+    let press = |app: &mut App, key_code: KeyCode, state: ButtonState| {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+        });
+    };
+
+    press(&mut app, KeyCode::W, ButtonState::Pressed);
+    app.update();
+    press(&mut app, KeyCode::W, ButtonState::Released);
+    app.update();
+    press(&mut app, KeyCode::B, ButtonState::Pressed);
+    app.update();
+    press(&mut app, KeyCode::B, ButtonState::Released);
+
+    let mut fireball_query = app
+        .world
+        .query_filtered::<(Entity, &Velocity, &Transform), With<Fireball>>();
+    let (fireball, velocity, transform) = fireball_query.single(&app.world);
+    assert!(velocity.y > 0.0 && velocity.x.abs() < 1e-6);
+    let (_, angle) = transform.rotation.to_axis_angle();
+    assert!((angle - PI / 2.0).abs() < 1e-4);
+
+    // gone once it leaves the top of the playfield
+    for _ in 0..120 {
+        app.update();
+    }
+    assert!(app.world.get_entity(fireball).is_none());
+}
+
+#[test]
+fn ground_covers_the_playfield_as_it_scrolls() {
+    let mut app = playing_app();
+    app.update();
+
+    let covered = |app: &mut App| {
+        let mut chunk_query = app.world.query::<(&GroundChunk, &Transform)>();
+        let mut spans: Vec<(f32, f32)> = chunk_query
+            .iter(&app.world)
+            .map(|(chunk, transform)| {
+                let x = transform.translation.x;
+                (x - chunk.width / 2.0, x + chunk.width / 2.0)
+            })
+            .collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut reached = -640.0;
+        for (left, right) in spans {
+            if left > reached + 0.01 {
+                break;
+            }
+            reached = reached.max(right);
+        }
+        reached >= 640.0
+    };
+    assert!(covered(&mut app));
+
+    let mut chunk_query = app
+        .world
+        .query_filtered::<(Entity, &Transform), With<GroundChunk>>();
+    let (chunk, chunk_transform) = chunk_query.iter(&app.world).next().unwrap();
+    let chunk_x = chunk_transform.translation.x;
+    app.update();
+    assert!(app.world.get::<Transform>(chunk).unwrap().translation.x < chunk_x);
+
+    // push the ground a chunk along, as if it had scrolled for a while
+    let chunk_width = app.world.get::<GroundChunk>(chunk).unwrap().width;
+    let mut chunks: Vec<_> = chunk_query
+        .iter(&app.world)
+        .map(|(entity, transform)| (entity, transform.translation.x))
+        .collect();
+    chunks.sort_by(|a, b| a.1.total_cmp(&b.1));
+    for (entity, _) in chunks.iter() {
+        let mut chunk = app.world.entity_mut(*entity);
+        chunk.get_mut::<Transform>().unwrap().translation.x -= chunk_width;
+        // or the tick would put it back where it was drawn last
+        *chunk.get_mut::<Interpolated>().unwrap() = Interpolated::default();
+    }
+    app.update();
+
+    let (leftmost, _) = chunks[0];
+    assert!(app.world.get_entity(leftmost).is_none());
+    assert!(covered(&mut app));
+}
+
+#[test]
+fn parallax_layers_scroll_at_their_own_pace_and_wrap() {
+    let mut app = playing_app();
+    app.update();
+
+    let mut sprite_query = app.world.query::<(Entity, &ParallaxSprite, &Transform)>();
+    let mut sprites = |app: &mut App| -> Vec<(Entity, usize, Vec3)> {
+        sprite_query
+            .iter(&app.world)
+            .map(|(entity, sprite, transform)| (entity, sprite.layer, transform.translation))
+            .collect()
+    };
+    let before = sprites(&mut app);
+    for layer in 0..PARALLAX_LAYERS.len() {
+        assert!(before
+            .iter()
+            .any(|(_, sprite_layer, _)| *sprite_layer == layer));
+    }
+
+    app.update();
+    let after = sprites(&mut app);
+    let mut steps = vec![0.0; PARALLAX_LAYERS.len()];
+    for (entity, layer, translation) in before.iter() {
+        let (_, _, moved_to) = after.iter().find(|(other, _, _)| other == entity).unwrap();
+        steps[*layer] = translation.x - moved_to.x;
+    }
+    // further back is slower
+    assert!(steps[0] > 0.0);
+    assert!(steps.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // a decoration that goes off the left comes round on the right
+    let (entity, layer, _) = after[0];
+    let sheet = &app
+        .world
+        .resource::<SpriteRegistry>()
+        .get(PARALLAX_LAYERS[layer].sprite)
+        .sheet;
+    let band = 640.0 + sheet.width * sheet.scale * PARALLAX_LAYERS[layer].scale;
+    {
+        let mut sprite = app.world.entity_mut(entity);
+        sprite.get_mut::<Transform>().unwrap().translation.x = -band;
+        *sprite.get_mut::<Interpolated>().unwrap() = Interpolated::default();
+    }
+    app.update();
+    assert!(app.world.get::<Transform>(entity).unwrap().translation.x > 640.0);
+    let layer_count = |sprites: &[(Entity, usize, Vec3)]| {
+        sprites
+            .iter()
+            .filter(|(_, sprite_layer, _)| *sprite_layer == layer)
+            .count()
+    };
+    assert_eq!(layer_count(&sprites(&mut app)), layer_count(&after));
+}
+
+#[test]
+fn boss_comes_in_on_its_wave_and_wins_the_game() {
+    let mut app = playing_app();
+
+    // as if the wave before the boss's had just been cleared
+    {
+        let mut wave_director = app.world.resource_mut::<WaveDirector>();
+        wave_director.number = 4;
+        wave_director.phase = WavePhase::Intermission;
+        wave_director.pause = Timer::from_seconds(60.0, TimerMode::Once);
+    }
+    app.update();
+    app.update();
+
+    assert_eq!(
+        *app.world.resource::<BossEncounter>(),
+        BossEncounter::Fighting
+    );
+    let mut boss_query = app.world.query_filtered::<Entity, With<Boss>>();
+    let boss = boss_query.single(&app.world);
+    let mut hud_query = app.world.query_filtered::<(), With<BossHud>>();
+    assert_eq!(hud_query.iter(&app.world).count(), 1);
+
+    // let it walk on screen, then leave it one hit from defeat
+    for _ in 0..600 {
+        if app.world.get::<Transform>(boss).unwrap().translation.x < 500.0 {
+            break;
+        }
+        app.update();
+    }
+    app.world.get_mut::<Health>(boss).unwrap().current = 1;
+    app.update();
+    assert!(app.world.get::<Boss>(boss).unwrap().phase > 0);
+
+    // a reload that takes phases away mid-fight leaves it on the last one
+    app.world
+        .resource_mut::<EnemyTypes>()
+        .boss
+        .as_mut()
+        .unwrap()
+        .phases
+        .truncate(1);
+    app.update();
+    assert_eq!(app.world.get::<Boss>(boss).unwrap().phase, 0);
+
+    // the waves held off while the boss was out
+    assert_eq!(app.world.resource::<WaveDirector>().number, 4);
+
+    let fireball = app
+        .world
+        .spawn((
+            Fireball,
+            PlayerVariant::One,
+            Damage {
+                amount: 1,
+                knockback: 0.0,
+            },
+            Velocity::default(),
+        ))
+        .id();
+    app.world.send_event(FireballHitEnemy {
+        fireball,
+        enemy: boss,
+    });
+    app.update();
+    app.update();
+
+    assert!(app.world.get_entity(boss).is_none());
+    assert_eq!(
+        *app.world.resource::<BossEncounter>(),
+        BossEncounter::Defeated
+    );
+    assert_eq!(
+        app.world.resource::<State<GameState>>().0,
+        GameState::Victory
+    );
+    assert_eq!(hud_query.iter(&app.world).count(), 0);
+
+    let mut score_query = app.world.query_filtered::<&Score, With<Player>>();
+    assert!(score_query.single(&app.world).value >= 100);
+}